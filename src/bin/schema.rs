@@ -1,6 +1,14 @@
-use cosmwasm_schema::write_api;
+use std::{
+    env,
+    fs::{create_dir_all, write},
+};
 
-use bidwasm::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cosmwasm_schema::{generate_api, write_api};
+
+use bidwasm::{
+    factory::msg as factory,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+};
 
 fn main() {
     write_api! {
@@ -8,4 +16,28 @@ fn main() {
         execute: ExecuteMsg,
         query: QueryMsg,
     }
+
+    // The factory schema goes to its own directory, so that it does not
+    // overwrite the raw files of the auction schema
+    let out_dir = env::current_dir().unwrap().join("schema").join("factory");
+    let raw_dir = out_dir.join("raw");
+    create_dir_all(&raw_dir).unwrap();
+
+    let api = generate_api! {
+        name: "bidwasm-factory",
+        instantiate: factory::InstantiateMsg,
+        execute: factory::ExecuteMsg,
+        query: factory::QueryMsg,
+    }
+    .render();
+
+    let path = out_dir.join("bidwasm-factory.json");
+    write(&path, api.to_string().unwrap() + "\n").unwrap();
+    println!("Exported the full API as {}", path.to_str().unwrap());
+
+    for (filename, json) in api.to_schema_files().unwrap() {
+        let path = raw_dir.join(filename);
+        write(&path, json + "\n").unwrap();
+        println!("Exported {}", path.to_str().unwrap());
+    }
 }
@@ -1,4 +1,4 @@
-#[cfg(not(feature = "library"))]
+#[cfg(not(any(feature = "library", feature = "factory")))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
 use cw2::set_contract_version;
@@ -6,81 +6,138 @@ use cw2::set_contract_version;
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Config, State, Status, CONFIG, STATE};
 use crate::{exec, query};
 
 // version info for migration info
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-#[cfg_attr(not(feature = "library"), entry_point)]
+#[cfg_attr(not(any(feature = "library", feature = "factory")), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    // If commission is passed as an argument, use it. Otherwise, use 0
-    let commission = msg.commission.unwrap_or_default();
-
-    // Current state for the auction is an "open" status and no bid
-    STATE.save(
-        deps.storage,
-        &State {
-            current_status: Status::Open,
-            highest_bid: None,
-        },
-    )?;
-
-    // If the owner is not passed to the instantiate function, use the sender
-    // address as the owner one
-    let owner = match msg.owner {
-        Some(str_owner) => deps.api.addr_validate(&str_owner)?,
-        None => info.sender,
-    };
-
-    // The configuration for the auction corresponds to:
-    //  - passed denom for the bid tokens;
-    //  - the owner address for the auction, which is able to close the auction
-    //  but cannot partecipate in bid requests;
-    //  - the description of the auction.
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            denom: msg.denom,
-            owner,
-            description: msg.description,
-            commission,
-        },
-    )?;
-
-    Ok(Response::new())
+    exec::create_auction(deps, env, info, msg)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
+#[cfg_attr(not(any(feature = "library", feature = "factory")), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    use ExecuteMsg::{Bid, Close, Retract};
+    use ExecuteMsg::{
+        AcceptOwnership, AddOperator, Bid, BidUnits, BuyNow, Cancel, ClaimFees, Close, Commit,
+        CreateAuction, Receive, ReceiveNft, RemoveOperator, RenounceOwnership, Retract, Reveal,
+        SetMerkleRoot, Settle, TransferOwnership, UpdateAccessList, UpdateMetadata,
+    };
 
     match msg {
-        Bid {} => exec::bid(deps, info),
-        Close {} => exec::close(deps, info),
-        Retract { recipient } => exec::retract(deps, info, recipient),
+        CreateAuction(msg) => exec::create_auction(deps, env, info, *msg),
+        Bid {
+            auction_id,
+            proof,
+            token_id,
+        } => exec::bid(deps, env, info, auction_id, proof, token_id),
+        BidUnits {
+            auction_id,
+            price,
+            quantity,
+        } => exec::bid_units(deps, env, info, auction_id, price, quantity),
+        BuyNow { auction_id, proof } => exec::buy_now(deps, env, info, auction_id, proof),
+        Commit { auction_id, hash } => exec::commit(deps, env, info, auction_id, hash),
+        Reveal {
+            auction_id,
+            amount,
+            salt,
+        } => exec::reveal(deps, env, info, auction_id, amount, salt),
+        Close { auction_id } => exec::close(deps, env, info, auction_id),
+        Cancel { auction_id, reason } => exec::cancel(deps, env, info, auction_id, reason),
+        TransferOwnership {
+            auction_id,
+            new_owner,
+            expiry,
+        } => exec::transfer_ownership(deps, env, info, auction_id, new_owner, expiry),
+        AcceptOwnership { auction_id } => exec::accept_ownership(deps, env, info, auction_id),
+        RenounceOwnership { auction_id } => exec::renounce_ownership(deps, info, auction_id),
+        AddOperator {
+            auction_id,
+            operator,
+            permissions,
+        } => exec::add_operator(deps, info, auction_id, operator, permissions),
+        RemoveOperator {
+            auction_id,
+            operator,
+        } => exec::remove_operator(deps, info, auction_id, operator),
+        UpdateMetadata {
+            auction_id,
+            description,
+        } => exec::update_metadata(deps, info, auction_id, description),
+        UpdateAccessList {
+            auction_id,
+            add,
+            remove,
+        } => exec::update_access_list(deps, info, auction_id, add, remove),
+        SetMerkleRoot {
+            auction_id,
+            merkle_root,
+        } => exec::set_merkle_root(deps, info, auction_id, merkle_root),
+        Settle { auction_id } => exec::settle(deps, env, info, auction_id),
+        ClaimFees { auction_id } => exec::claim_fees(deps, info, auction_id),
+        Retract {
+            auction_id,
+            recipient,
+        } => exec::retract(deps, env, info, auction_id, recipient),
+        Receive(msg) => exec::receive(deps, env, info, msg),
+        ReceiveNft(msg) => exec::receive_nft(deps, info, msg),
     }
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+#[cfg_attr(not(any(feature = "library", feature = "factory")), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::TotalBid { address } => to_binary(&query::total_bid(deps, address)?),
-        QueryMsg::HighestBid {} => to_binary(&query::highest_bid(deps)?),
-        QueryMsg::IsClosed {} => to_binary(&query::is_closed(deps)?),
-        QueryMsg::Winner {} => to_binary(&query::winner(deps)?),
+        QueryMsg::TotalBid {
+            auction_id,
+            address,
+        } => to_binary(&query::total_bid(deps, auction_id, address)?),
+        QueryMsg::HighestBid { auction_id } => to_binary(&query::highest_bid(deps, auction_id)?),
+        QueryMsg::IsClosed { auction_id } => to_binary(&query::is_closed(deps, env, auction_id)?),
+        QueryMsg::Winner { auction_id } => to_binary(&query::winner(deps, env, auction_id)?),
+        QueryMsg::Schedule { auction_id } => to_binary(&query::schedule(deps, auction_id)?),
+        QueryMsg::MinNextBid { auction_id } => to_binary(&query::min_next_bid(deps, auction_id)?),
+        QueryMsg::AccruedFees { auction_id } => to_binary(&query::accrued_fees(deps, auction_id)?),
+        QueryMsg::Lot { auction_id } => to_binary(&query::lot(deps, auction_id)?),
+        QueryMsg::Commitment {
+            auction_id,
+            address,
+        } => to_binary(&query::commitment(deps, auction_id, address)?),
+        QueryMsg::CurrentPrice { auction_id } => {
+            to_binary(&query::current_price(deps, env, auction_id)?)
+        }
+        QueryMsg::Allocations { auction_id } => to_binary(&query::allocations(deps, auction_id)?),
+        QueryMsg::Ownership { auction_id } => to_binary(&query::ownership(deps, auction_id)?),
+        QueryMsg::Operators { auction_id } => to_binary(&query::operators(deps, auction_id)?),
+        QueryMsg::Gating { auction_id } => to_binary(&query::gating(deps, auction_id)?),
+        QueryMsg::AccessList {
+            auction_id,
+            start_after,
+            limit,
+        } => to_binary(&query::access_list(deps, auction_id, start_after, limit)?),
+        QueryMsg::ListAuctions {
+            status,
+            start_after,
+            limit,
+        } => to_binary(&query::list_auctions(
+            deps,
+            env,
+            status,
+            start_after,
+            limit,
+        )?),
     }
 }
@@ -1,4 +1,5 @@
 use cosmwasm_std::{StdError, Uint128};
+use cw_utils::{ParseReplyError, Scheduled};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +7,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
     #[error("Unauthorized - only {owner} can perform this action")]
     Unauthorized { owner: String },
 
@@ -15,12 +19,106 @@ pub enum ContractError {
     #[error("Invalid retract: the user cannot perform this action")]
     InvalidRetract,
 
+    #[error("Invalid claim: the user has not any accrued fee")]
+    NoAccruedFees,
+
     #[error("Auction is already closed")]
     ClosedAcution,
 
     #[error("Auction is yet open")]
     OpenAcution,
 
+    #[error("Auction is waiting for its lot to be deposited")]
+    PendingAuction,
+
+    #[error("Invalid lot: the deposited asset is not the auction lot")]
+    InvalidLot,
+
+    #[error("Auction is not started yet - bids are accepted from {start}")]
+    NotStartedAuction { start: Scheduled },
+
+    #[error("Invalid schedule: auction end must be in the future and after its start")]
+    InvalidSchedule,
+
+    #[error("Invalid commission: rates cannot exceed 10000 bps, bounds must be ordered and tiers sorted")]
+    InvalidCommission,
+
+    #[error(
+        "Invalid commission split: recipients must be distinct with positive shares summing to 1"
+    )]
+    InvalidCommissionSplit,
+
+    #[error("Invalid soft close: it requires an auction end expressed as a block time")]
+    InvalidSoftClose,
+
+    #[error("Invalid Dutch auction: the start price cannot be below the floor, the price must fall over time and bid rules of ascending auctions are not supported")]
+    InvalidDutchAuction,
+
+    #[error("Invalid sealed auction: bids must be committed before the auction end, which cannot be extended, and cannot be raised nor have a starting price")]
+    InvalidSealedAuction,
+
+    #[error("Invalid multi-unit auction: the supply cannot be 0, and it is sold at a uniform price through ascending bids without an on-chain lot")]
+    InvalidMultiUnitAuction,
+
+    #[error("Invalid bid: multi-unit auctions take bids for 1 up to the supply units, which cannot lower the price or quantity of a previous bid")]
+    InvalidUnitBid,
+
+    #[error("Invalid buy-it-now price: it is only supported by single lot ascending auctions, and cannot be below the reserve or starting price")]
+    InvalidBuyNowPrice,
+
+    #[error("Auction has no buy-it-now price")]
+    NoBuyNowPrice,
+
+    #[error("Invalid ownership transfer: {owner} has bids on the auction")]
+    OwnerWithBids { owner: String },
+
+    #[error("Invalid operator: {operator} has bids on the auction")]
+    OperatorWithBids { operator: String },
+
+    #[error("Invalid operator: at least one permission must be granted")]
+    EmptyPermissions,
+
+    #[error("{address} is not an operator of the auction")]
+    NotOperator { address: String },
+
+    #[error("Invalid bid: {bidder} is not allowed to bid on the auction")]
+    BidderNotAllowed { bidder: String },
+
+    #[error("Invalid Merkle root: it must be a sha256 hash, on an auction taking plain bids")]
+    InvalidMerkleRoot,
+
+    #[error("Invalid bid: {bidder} has no valid Merkle proof of eligibility")]
+    NotEligible { bidder: String },
+
+    #[error(
+        "Invalid bid: {bidder} is not a member of the group with a weight of at least {min_weight}"
+    )]
+    NotGroupMember { bidder: String, min_weight: u64 },
+
+    #[error("Invalid bid: {bidder} holds no token of the collection {collection}")]
+    NotNftHolder { bidder: String, collection: String },
+
+    #[error("Invalid bid: Operator {operator} cannot perform this action")]
+    OperatorBid { operator: String },
+
+    #[error("There is not any pending ownership transfer to the sender")]
+    NoPendingOwnership,
+
+    #[error("Ownership transfer has expired")]
+    OwnershipExpired,
+
+    #[error("Invalid renounce: the auction could not be closed anymore without an end")]
+    InvalidRenounce,
+
+    #[error("Action not allowed in the current phase of the auction")]
+    InvalidPhase,
+
+    #[error("Invalid commitment: the user has already committed a bid")]
+    AlreadyCommitted,
+
+    #[error("Invalid reveal: the bid does not match the commitment of the user")]
+    InvalidReveal,
+
     #[error(
         "Invalid bid: Amount of token sent ({funds}) are lower than commission ({commission})"
     )]
@@ -29,6 +127,9 @@ pub enum ContractError {
     #[error("Invalid bid: Amount of token sent cannot be 0")]
     InsufficientFunds,
 
+    #[error("Invalid bid: {token} is not the token of the auction")]
+    InvalidToken { token: String },
+
     #[error("Invalid bid: Proposed bid (existing + new bid = {existing} + {funds} = {new_bid}) is lower than actual maximum bid ({max_bid})")]
     InsufficientBid {
         existing: Uint128,
@@ -36,4 +137,40 @@ pub enum ContractError {
         new_bid: Uint128,
         max_bid: Uint128,
     },
+
+    #[error(
+        "Invalid bid: Proposed bid ({new_bid}) is lower than the minimum next bid ({min_bid})"
+    )]
+    InsufficientIncrement { new_bid: Uint128, min_bid: Uint128 },
+
+    #[error("Invalid reveal: Deposit ({deposit}) is lower than the bid ({amount}) plus its commission ({commission})")]
+    InsufficientDeposit {
+        deposit: Uint128,
+        amount: Uint128,
+        commission: Uint128,
+    },
+
+    #[error("Invalid cancel: Commissions to pay back ({required}) do not match the amount of token sent ({funds})")]
+    InvalidCancelFunds { required: Uint128, funds: Uint128 },
+
+    #[error(
+        "Invalid buy now: Proposed bid ({new_bid}) is lower than the buy-it-now price ({price})"
+    )]
+    BelowBuyNowPrice { new_bid: Uint128, price: Uint128 },
+
+    #[error(
+        "Invalid bid: Deposit ({deposit}) is lower than the price ({price}) of {quantity} units"
+    )]
+    InsufficientUnitDeposit {
+        deposit: Uint128,
+        price: Uint128,
+        quantity: u64,
+    },
+
+    #[error("Invalid bid: Amount of token sent ({funds}) is lower than the current price ({price}) plus its commission ({commission})")]
+    BelowCurrentPrice {
+        funds: Uint128,
+        price: Uint128,
+        commission: Uint128,
+    },
 }
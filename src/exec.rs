@@ -1,43 +1,584 @@
-use cosmwasm_std::{coins, BankMsg, DepsMut, MessageInfo, Response, StdResult, Uint128};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Decimal, DepsMut, Env,
+    MessageInfo, Order, QuerierWrapper, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw4::{Cw4QueryMsg, MemberResponse};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, Cw721ReceiveMsg, OwnerOfResponse, TokensResponse};
+use cw_utils::{Expiration, Scheduled};
 
 use crate::{
-    state::{Status, BIDS, CONFIG, STATE},
+    msg::{InstantiateMsg, ReceiveMsg, UncheckedLot},
+    state::{
+        allocate, commitment_hash, verify_merkle_proof, AccessControl, AuctionKind, CommissionMode,
+        Commitment, Config, GroupGate, Lot, PendingOwner, Permission, Pricing, SealedAuction,
+        State, Status, UnitBid, UnrevealedDeposits, ACCESS_LIST, ACCRUED_FEES, AUCTION_COUNT, BIDS,
+        COMMISSIONS, COMMITMENTS, CONFIGS, OPERATORS, PENDING_OWNERS, STATES, UNIT_BIDS, VERIFIED,
+    },
     ContractError,
 };
 
-pub fn bid(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    let owner = CONFIG.load(deps.storage)?.owner;
-    let commission = Uint128::new(CONFIG.load(deps.storage)?.commission);
-    let denom = CONFIG.load(deps.storage)?.denom;
+// Store a new auction under the next available id
+pub fn create_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let id = AUCTION_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    AUCTION_COUNT.save(deps.storage, &(id + 1))?;
 
-    let mut state = STATE.load(deps.storage)?;
-    let highest_bid = state.highest_bid;
+    // If commission is passed as an argument, use it. Otherwise, use 0
+    let commission = msg.commission.unwrap_or_default();
+    if !commission.is_valid() {
+        return Err(ContractError::InvalidCommission);
+    }
 
-    let mut resp = Response::new();
+    // The auction end must not be already passed and, when start and end are
+    // expressed with the same unit, it must come after the start
+    if let Some(end) = &msg.end {
+        let ends_before_start = msg.start.as_ref().is_some_and(|start| end <= start);
+        if end.is_triggered(&env.block) || ends_before_start {
+            return Err(ContractError::InvalidSchedule);
+        }
+    }
 
-    // If auction is already closed, then bid cannot be processed
-    if state.current_status == Status::Closed {
-        return Err(ContractError::ClosedAcution);
+    // Extending the end on late bids is only supported for time based ends
+    if msg.soft_close.is_some() && !matches!(msg.end, Some(Scheduled::AtTime(_))) {
+        return Err(ContractError::InvalidSoftClose);
     }
 
-    // Owner of the auction cannot bid
-    if owner == info.sender {
-        return Err(ContractError::InvalidBid {
-            owner: owner.to_string(),
+    // A Dutch auction sells at its current price, so the rules on ascending
+    // bids do not apply to it
+    let kind = msg.kind.unwrap_or_default();
+    if let AuctionKind::Dutch(dutch) = &kind {
+        if !dutch.is_valid()
+            || msg.pricing == Some(Pricing::SecondPrice)
+            || msg.soft_close.is_some()
+            || msg.reserve_price.is_some()
+            || msg.starting_price.is_some()
+            || msg.min_increment.is_some()
+        {
+            return Err(ContractError::InvalidDutchAuction);
+        }
+    }
+
+    // Sealed bids are committed before the auction end (when expressed with
+    // the same unit) and revealed until it, so they cannot extend it nor be
+    // raised. Their minimum is the reserve price, as a starting price would
+    // only be known to be missed once the bids are revealed.
+    if let AuctionKind::Sealed(sealed) = &kind {
+        let commits_before_end = msg.end.as_ref().is_some_and(|end| sealed.commit_end < *end);
+        if !commits_before_end
+            || sealed.commit_end.is_triggered(&env.block)
+            || msg.soft_close.is_some()
+            || msg.min_increment.is_some()
+            || msg.starting_price.is_some()
+        {
+            return Err(ContractError::InvalidSealedAuction);
+        }
+    }
+
+    // The units of a multi-unit auction go to the highest ascending bids at a
+    // uniform price, so there is no single lot nor winning bid to raise
+    if let Some(quantity) = msg.quantity {
+        if quantity == 0
+            || kind != AuctionKind::English
+            || msg.pricing == Some(Pricing::SecondPrice)
+            || msg.lot.is_some()
+            || msg.min_increment.is_some()
+        {
+            return Err(ContractError::InvalidMultiUnitAuction);
+        }
+    }
+
+    // The buy-it-now price takes the single lot of an ascending auction, so it
+    // must meet the reserve and starting price
+    if let Some(buy_now_price) = msg.buy_now_price {
+        if kind != AuctionKind::English
+            || msg.quantity.is_some()
+            || msg.reserve_price.is_some_and(|price| buy_now_price < price)
+            || msg
+                .starting_price
+                .is_some_and(|price| buy_now_price < price)
+            || buy_now_price.is_zero()
+        {
+            return Err(ContractError::InvalidBuyNowPrice);
+        }
+    }
+
+    if let Some(merkle_root) = &msg.merkle_root {
+        check_merkle_root(merkle_root, &kind, msg.quantity)?;
+    }
+
+    // An on-chain lot must be deposited before the auction opens, except for
+    // native coins which are sent along with the instantiation
+    let lot = msg
+        .lot
+        .map(|lot| -> Result<_, ContractError> {
+            match lot {
+                UncheckedLot::Native(coins) => {
+                    let is_funded = coins.len() == info.funds.len()
+                        && coins.iter().all(|coin| info.funds.contains(coin));
+                    if coins.is_empty()
+                        || coins.iter().any(|coin| coin.amount.is_zero())
+                        || !is_funded
+                    {
+                        return Err(ContractError::InvalidLot);
+                    }
+                    Ok(Lot::Native(coins))
+                }
+                UncheckedLot::Cw20 { token, amount } => {
+                    if amount.is_zero() {
+                        return Err(ContractError::InvalidLot);
+                    }
+                    Ok(Lot::Cw20 {
+                        token: deps.api.addr_validate(&token)?,
+                        amount,
+                    })
+                }
+                UncheckedLot::Nft {
+                    collection,
+                    token_id,
+                } => Ok(Lot::Nft {
+                    collection: deps.api.addr_validate(&collection)?,
+                    token_id,
+                }),
+            }
+        })
+        .transpose()?;
+    let current_status = match lot {
+        Some(Lot::Cw20 { .. } | Lot::Nft { .. }) => Status::Pending,
+        Some(Lot::Native(_)) | None => Status::Open,
+    };
+
+    // Current state for the auction is an "open" status (or "pending" until
+    // the lot is deposited) and no bid
+    STATES.save(
+        deps.storage,
+        id,
+        &State {
+            current_status,
+            highest_bid: None,
+            runner_up: None,
+            commission_pool: Uint128::zero(),
+            end: msg.end,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
+        },
+    )?;
+
+    // If the owner is not passed to the instantiate function, use the sender
+    // address as the owner one
+    let owner = match msg.owner {
+        Some(str_owner) => deps.api.addr_validate(&str_owner)?,
+        None => info.sender,
+    };
+
+    // Commission recipients must be distinct, with positive shares summing to 1
+    let commission_recipients = msg
+        .commission_recipients
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(recipient, share)| Ok((deps.api.addr_validate(&recipient)?, share)))
+        .collect::<StdResult<Vec<_>>>()?;
+    if !commission_recipients.is_empty() {
+        let total: Decimal = commission_recipients.iter().map(|(_, share)| *share).sum();
+        let has_duplicates = commission_recipients
+            .iter()
+            .enumerate()
+            .any(|(i, (recipient, _))| {
+                commission_recipients[..i]
+                    .iter()
+                    .any(|(other, _)| other == recipient)
+            });
+        if total != Decimal::one()
+            || has_duplicates
+            || commission_recipients
+                .iter()
+                .any(|(_, share)| share.is_zero())
+        {
+            return Err(ContractError::InvalidCommissionSplit);
+        }
+    }
+
+    // A CW20 bidding token must be a valid token contract
+    let denom = msg.denom.into_checked(deps.as_ref())?;
+
+    let proceeds_recipient = msg
+        .proceeds_recipient
+        .map(|recipient| deps.api.addr_validate(&recipient))
+        .transpose()?;
+
+    let nft_gate = msg
+        .nft_gate
+        .map(|collection| deps.api.addr_validate(&collection))
+        .transpose()?;
+
+    let group = msg
+        .group
+        .map(|gate| -> StdResult<_> {
+            Ok(GroupGate {
+                group: deps.api.addr_validate(&gate.group)?,
+                min_weight: gate.min_weight,
+            })
+        })
+        .transpose()?;
+
+    // The configuration for the auction corresponds to:
+    //  - passed denom for the bid tokens, native or CW20;
+    //  - the owner address for the auction, which is able to close the auction
+    //  but cannot partecipate in bid requests;
+    //  - the description of the auction;
+    //  - the optional bidding window;
+    //  - the reward for who settles the auction after its end;
+    //  - the optional soft close rules;
+    //  - the optional reserve price;
+    //  - the optional starting price and minimum increment;
+    //  - the optional recipients of commissions and of the winning bid;
+    //  - how commissions reach their recipients;
+    //  - the optional on-chain lot;
+    //  - the kind of auction, with its creation time;
+    //  - the price paid by the winner;
+    //  - the optional number of units on sale;
+    //  - the optional buy-it-now price;
+    //  - whether commissions are refunded if the auction is cancelled;
+    //  - who is allowed to bid;
+    //  - the optional Merkle root of the eligible bidders;
+    //  - the optional cw4 group of the eligible bidders;
+    //  - the optional CW721 collection the bidders must hold a token of.
+    CONFIGS.save(
+        deps.storage,
+        id,
+        &Config {
+            denom,
+            owner,
+            description: msg.description,
+            commission,
+            start: msg.start,
+            end: msg.end,
+            keeper_reward: msg.keeper_reward.unwrap_or_default(),
+            soft_close: msg.soft_close,
+            reserve_price: msg.reserve_price,
+            starting_price: msg.starting_price,
+            min_increment: msg.min_increment,
+            commission_recipients,
+            proceeds_recipient,
+            commission_mode: msg.commission_mode.unwrap_or_default(),
+            lot,
+            kind,
+            pricing: msg.pricing.unwrap_or_default(),
+            quantity: msg.quantity,
+            buy_now_price: msg.buy_now_price,
+            refund_commissions: msg.refund_commissions.unwrap_or_default(),
+            renounced: false,
+            access: msg.access.unwrap_or_default(),
+            merkle_root: msg.merkle_root,
+            group,
+            nft_gate,
+            created_at: env.block.time,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "create_auction")
+        .add_attribute("auction_id", id.to_string());
+    Ok(resp)
+}
+
+pub fn bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    proof: Option<Vec<Binary>>,
+    token_id: Option<String>,
+) -> Result<Response, ContractError> {
+    let denom = CONFIGS.load(deps.storage, auction_id)?.denom;
+    let funds = native_funds(&denom, &info)?;
+
+    place_bid(
+        deps,
+        env,
+        auction_id,
+        info.sender,
+        funds,
+        proof,
+        token_id,
+        false,
+    )
+}
+
+pub fn buy_now(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    proof: Option<Vec<Binary>>,
+) -> Result<Response, ContractError> {
+    let denom = CONFIGS.load(deps.storage, auction_id)?.denom;
+    let funds = native_funds(&denom, &info)?;
+
+    place_bid(deps, env, auction_id, info.sender, funds, proof, None, true)
+}
+
+pub fn bid_units(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    price: Uint128,
+    quantity: u64,
+) -> Result<Response, ContractError> {
+    let denom = CONFIGS.load(deps.storage, auction_id)?.denom;
+    let funds = native_funds(&denom, &info)?;
+
+    place_unit_bid(deps, env, auction_id, info.sender, funds, price, quantity)
+}
+
+pub fn commit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    hash: Binary,
+) -> Result<Response, ContractError> {
+    let denom = CONFIGS.load(deps.storage, auction_id)?.denom;
+    let deposit = native_funds(&denom, &info)?;
+
+    place_commitment(deps, env, auction_id, info.sender, deposit, hash)
+}
+
+// Retrieve the native funds sent in the auction denom. Native funds are never
+// valid for an auction priced in a CW20 token.
+fn native_funds(denom: &Denom, info: &MessageInfo) -> Result<Uint128, ContractError> {
+    let funds = match denom {
+        Denom::Native(denom) => info.funds.iter().find(|coin| coin.denom == *denom),
+        Denom::Cw20(_) => None,
+    };
+    match funds {
+        Some(funds) => Ok(funds.amount),
+        None => Err(ContractError::InsufficientFunds),
+    }
+}
+
+pub fn receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+
+    let auction_id = match &msg {
+        ReceiveMsg::Bid { auction_id, .. }
+        | ReceiveMsg::BidUnits { auction_id, .. }
+        | ReceiveMsg::BuyNow { auction_id, .. }
+        | ReceiveMsg::Commit { auction_id, .. }
+        | ReceiveMsg::DepositLot { auction_id }
+        | ReceiveMsg::Cancel { auction_id, .. } => *auction_id,
+    };
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+
+    // Apart from the lot, only the CW20 token the auction is priced in can be
+    // sent
+    let is_lot = matches!(msg, ReceiveMsg::DepositLot { .. });
+    if !is_lot && config.denom != Denom::Cw20(info.sender.clone()) {
+        return Err(ContractError::InvalidToken {
+            token: info.sender.into_string(),
+        });
+    }
+
+    match msg {
+        ReceiveMsg::Bid {
+            auction_id,
+            proof,
+            token_id,
+        } => place_bid(
+            deps,
+            env,
+            auction_id,
+            sender,
+            wrapper.amount,
+            proof,
+            token_id,
+            false,
+        ),
+        ReceiveMsg::BuyNow { auction_id, proof } => place_bid(
+            deps,
+            env,
+            auction_id,
+            sender,
+            wrapper.amount,
+            proof,
+            None,
+            true,
+        ),
+        ReceiveMsg::BidUnits {
+            auction_id,
+            price,
+            quantity,
+        } => place_unit_bid(
+            deps,
+            env,
+            auction_id,
+            sender,
+            wrapper.amount,
+            price,
+            quantity,
+        ),
+        ReceiveMsg::Commit { auction_id, hash } => {
+            place_commitment(deps, env, auction_id, sender, wrapper.amount, hash)
+        }
+        ReceiveMsg::DepositLot { auction_id } => {
+            // Only the expected token and amount can be deposited
+            let expected = Lot::Cw20 {
+                token: info.sender,
+                amount: wrapper.amount,
+            };
+            if config.lot.as_ref() != Some(&expected) {
+                return Err(ContractError::InvalidLot);
+            }
+
+            deposit_lot(deps.storage, auction_id, &config, sender.as_str())
+        }
+        ReceiveMsg::Cancel { auction_id, reason } => {
+            cancel_auction(deps, env, auction_id, sender, wrapper.amount, reason)
+        }
+    }
+}
+
+pub fn receive_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    wrapper: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // The auction is identified by the message embedded in the send, and NFTs
+    // can only be deposited as a lot
+    let auction_id = match from_binary(&wrapper.msg)? {
+        ReceiveMsg::DepositLot { auction_id } => auction_id,
+        ReceiveMsg::Bid { .. }
+        | ReceiveMsg::BidUnits { .. }
+        | ReceiveMsg::BuyNow { .. }
+        | ReceiveMsg::Commit { .. }
+        | ReceiveMsg::Cancel { .. } => return Err(ContractError::InvalidLot),
+    };
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+
+    // Only the expected token can be deposited
+    let expected = Lot::Nft {
+        collection: info.sender,
+        token_id: wrapper.token_id,
+    };
+    if config.lot.as_ref() != Some(&expected) {
+        return Err(ContractError::InvalidLot);
+    }
+
+    deposit_lot(deps.storage, auction_id, &config, &wrapper.sender)
+}
+
+// Open the auction once the lot, already checked against the configured one,
+// has been transferred to the contract by `sender`
+fn deposit_lot(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    config: &Config,
+    sender: &str,
+) -> Result<Response, ContractError> {
+    let mut state = STATES.load(storage, auction_id)?;
+
+    // The lot can be deposited only once
+    if state.current_status != Status::Pending {
+        return Err(ContractError::InvalidLot);
+    }
+
+    // Only the owner of the auction can deposit the lot
+    if !config.is_owner(sender) {
+        return Err(ContractError::Unauthorized {
+            owner: config.owner.to_string(),
         });
     }
 
+    // The lot is escrowed, so the auction is open for bids
+    state.current_status = Status::Open;
+    STATES.save(storage, auction_id, &state)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "deposit_lot")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", sender);
+    Ok(resp)
+}
+
+// Process a bid of `funds` tokens, already transferred to the contract, on
+// behalf of `sender`. With `buy_now`, the bid fails unless it reaches the
+// buy-it-now price.
+#[allow(clippy::too_many_arguments)]
+fn place_bid(
+    mut deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    sender: Addr,
+    funds: Uint128,
+    proof: Option<Vec<Binary>>,
+    token_id: Option<String>,
+    buy_now: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let mut state = STATES.load(deps.storage, auction_id)?;
+
+    let mut resp = Response::new();
+
+    check_bidder(
+        deps.branch(),
+        &env,
+        auction_id,
+        &config,
+        &state,
+        &sender,
+        proof,
+        token_id,
+    )?;
+
+    // Only an auction with a buy-it-now price can be bought right away
+    if buy_now && config.buy_now_price.is_none() {
+        return Err(ContractError::NoBuyNowPrice);
+    }
+
+    // Bids of a multi-unit auction carry a per-unit price and a quantity
+    if config.quantity.is_some() {
+        return Err(ContractError::InvalidUnitBid);
+    }
+
+    // Bids of a sealed auction are only committed and revealed
+    if let AuctionKind::Sealed(_) = config.kind {
+        return Err(ContractError::InvalidPhase);
+    }
+
+    // A Dutch auction is won right away by the first bid covering its price
+    if let Some(price) = config.current_price(&env.block) {
+        return buy_at_price(
+            deps.storage,
+            auction_id,
+            &config,
+            state,
+            sender,
+            funds,
+            price,
+        );
+    }
+
     // Retrieve the highest bid or get a default value
-    let highest_bid_amount = match highest_bid {
+    let highest_bid_amount = match &state.highest_bid {
         Some(highest_bid) => highest_bid.1,
         None => Uint128::new(0),
     };
 
-    // Retrieve funds or provide a default value
-    let funds = match info.funds.iter().find(|coin| coin.denom == denom) {
-        Some(funds) => funds.amount,
-        None => return Err(ContractError::InsufficientFunds),
-    };
+    // Compute the commission due for the funds sent
+    let commission = config.commission.fee(funds);
 
     // If sender needs to pay a commission and the provided funds are less than
     // the required amount
@@ -50,15 +591,28 @@ pub fn bid(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError>
     let net_bid = funds - commission;
 
     // If the sender bid is greater than the current maximum bid
-    let existing_bid = match BIDS.may_load(deps.storage, &info.sender)? {
+    let existing_bid = match BIDS.may_load(deps.storage, (auction_id, &sender))? {
         Some(existing_bid) => existing_bid,
         None => Uint128::new(0),
     };
 
-    // If the total bid of the user is less or the same as the highest,
-    // bidding should fail
+    // Funds covering the rest of the buy-it-now price, along with the
+    // commission on it, take the lot at that price
     let new_bid = net_bid + existing_bid;
-    if new_bid <= highest_bid_amount {
+    let buy_now_price = config.buy_now_price.filter(|price| {
+        let kept = *price - existing_bid;
+        funds >= kept + config.commission.fee(kept)
+    });
+    match config.buy_now_price {
+        Some(price) if buy_now && buy_now_price.is_none() => {
+            return Err(ContractError::BelowBuyNowPrice { new_bid, price })
+        }
+        _ => {}
+    }
+
+    // If the total bid of the user is less or the same as the highest,
+    // bidding should fail, unless it buys the lot
+    if new_bid <= highest_bid_amount && buy_now_price.is_none() {
         return Err(ContractError::InsufficientBid {
             existing: existing_bid,
             funds,
@@ -67,130 +621,1446 @@ pub fn bid(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError>
         });
     }
 
+    // The total bid of the user must also reach the starting price and the
+    // minimum increment over the highest bid, unless it buys the lot
+    let min_bid = state.min_next_bid(&config);
+    if new_bid < min_bid && buy_now_price.is_none() {
+        return Err(ContractError::InsufficientIncrement { new_bid, min_bid });
+    }
+
+    // Whatever exceeds the buy-it-now price is refunded, and the commission is
+    // only due on the funds kept to reach it
+    let (new_bid, commission, refund) = match buy_now_price {
+        Some(price) => {
+            let kept = price - existing_bid;
+            let commission = config.commission.fee(kept);
+            (price, commission, funds - kept - commission)
+        }
+        None => (new_bid, commission, Uint128::zero()),
+    };
+
     // Otherwise we should process the bid
-    state.highest_bid = Some((info.sender.clone(), new_bid));
+    state.record_bid(&sender, new_bid);
 
-    // If there is any commission, we should send them to the contract owner
-    if !commission.is_zero() {
-        let funds: Vec<_> = coins(commission.u128(), denom);
+    if buy_now_price.is_none() {
+        resp = extend_end(&config, &mut state, &env, resp);
+    }
 
-        // Create a bank message to send funds to the contract owner
-        let commission_msg = BankMsg::Send {
-            to_address: owner.into_string(),
-            amount: funds,
-        };
+    track_commission(deps.storage, auction_id, &sender, commission)?;
 
-        resp = resp
-            .add_message(commission_msg)
-            .add_attribute("commission_payer", info.sender.as_str());
+    let commission = set_aside_keeper_share(&config, &mut state, commission);
+
+    // If there is any commission, we should send them to the commission
+    // recipients
+    if !commission.is_zero() {
+        resp = distribute_commission(deps.storage, auction_id, &config, commission, resp)?
+            .add_attribute("commission_payer", sender.as_str());
     }
 
     // Update the bid for the sender
-    BIDS.update(deps.storage, &info.sender, |_| -> StdResult<_> {
+    BIDS.update(deps.storage, (auction_id, &sender), |_| -> StdResult<_> {
         Ok(new_bid)
     })?;
 
+    // Reaching the buy-it-now price closes the auction as the owner would,
+    // every other bid stays refundable through retract
+    if buy_now_price.is_some() {
+        state.bought_now = true;
+        let closing = close_auction(deps.storage, auction_id, &config, &mut state)?;
+        resp = resp
+            .add_submessages(closing.messages)
+            .add_attributes(closing.attributes);
+
+        let pool = std::mem::take(&mut state.commission_pool);
+        if !pool.is_zero() {
+            resp = distribute_commission(deps.storage, auction_id, &config, pool, resp)?;
+        }
+
+        if !refund.is_zero() {
+            resp = resp
+                .add_message(send_tokens(&config.denom, &sender, refund)?)
+                .add_attribute("refund", refund);
+        }
+        resp = resp.add_attribute("outcome", "bought_now");
+    }
+
     // Update the state for the auction
-    STATE.save(deps.storage, &state)?;
+    STATES.save(deps.storage, auction_id, &state)?;
 
     resp = resp
         .add_attribute("action", "bid")
-        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", sender.as_str())
         .add_attribute("current_highest_bid", new_bid);
 
     Ok(resp)
 }
 
-pub fn close(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    let owner = CONFIG.load(deps.storage)?.owner;
+// Keep track of the commissions paid by each bidder, to refund them if the
+// auction is cancelled
+fn track_commission(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    bidder: &Addr,
+    commission: Uint128,
+) -> StdResult<()> {
+    if !commission.is_zero() {
+        COMMISSIONS.update(storage, (auction_id, bidder), |paid| -> StdResult<_> {
+            Ok(paid.unwrap_or_default() + commission)
+        })?;
+    }
+    Ok(())
+}
+
+// Set part of the commission aside until the keeper reward is covered,
+// returning what is left for the commission recipients
+fn set_aside_keeper_share(config: &Config, state: &mut State, commission: Uint128) -> Uint128 {
+    let keeper_share = commission.min(config.keeper_reward.saturating_sub(state.commission_pool));
+    state.commission_pool += keeper_share;
+    commission - keeper_share
+}
+
+// A bid landing in the soft close window moves the end forward, up to the
+// maximum total extension
+fn extend_end(config: &Config, state: &mut State, env: &Env, mut resp: Response) -> Response {
+    if let (Some(soft_close), Some(Scheduled::AtTime(end))) = (&config.soft_close, state.end) {
+        let step = soft_close
+            .extension
+            .min(soft_close.max_extension - state.extension);
+        if step > 0 && env.block.time.plus_seconds(soft_close.window) >= end {
+            let new_end = end.plus_seconds(step);
+            state.end = Some(Scheduled::AtTime(new_end));
+            state.extension += step;
+            resp = resp.add_attribute("end_extended_to", new_end.to_string());
+        }
+    }
+    resp
+}
 
-    let state = STATE.load(deps.storage)?;
-    let denom = CONFIG.load(deps.storage)?.denom;
+// Process a bid for `quantity` units at `price` each on behalf of `sender`,
+// along with `funds` tokens already transferred to the contract. A new bid
+// replaces the previous one of the sender, and the funds sent so far must
+// cover it.
+#[allow(clippy::too_many_arguments)]
+fn place_unit_bid(
+    mut deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    sender: Addr,
+    funds: Uint128,
+    price: Uint128,
+    quantity: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let mut state = STATES.load(deps.storage, auction_id)?;
 
     let mut resp = Response::new();
 
-    // If auction is already closed, then the action cannot be processed
-    if state.current_status == Status::Closed {
+    check_bidder(
+        deps.branch(),
+        &env,
+        auction_id,
+        &config,
+        &state,
+        &sender,
+        None,
+        None,
+    )?;
+
+    // The quantity must be available, and a new bid cannot lower the price or
+    // the quantity of the previous one
+    let previous = UNIT_BIDS.may_load(deps.storage, (auction_id, &sender))?;
+    let is_lowered = previous
+        .as_ref()
+        .is_some_and(|previous| price < previous.price || quantity < previous.quantity);
+    match config.quantity {
+        Some(supply) if (1..=supply).contains(&quantity) && !is_lowered => {}
+        _ => return Err(ContractError::InvalidUnitBid),
+    }
+
+    // The price must reach the starting price
+    let min_price = config
+        .starting_price
+        .unwrap_or_default()
+        .max(Uint128::one());
+    if price < min_price {
+        return Err(ContractError::InsufficientIncrement {
+            new_bid: price,
+            min_bid: min_price,
+        });
+    }
+
+    // Compute the commission due for the funds sent
+    let commission = config.commission.fee(funds);
+    if funds < commission {
+        return Err(ContractError::InsufficientFundsForCommission { funds, commission });
+    }
+
+    // The deposit of the sender, without commissions, must cover the bid
+    let deposit = BIDS
+        .may_load(deps.storage, (auction_id, &sender))?
+        .unwrap_or_default()
+        + funds
+        - commission;
+    if deposit < price.checked_mul(quantity.into()).map_err(StdError::from)? {
+        return Err(ContractError::InsufficientUnitDeposit {
+            deposit,
+            price,
+            quantity,
+        });
+    }
+
+    UNIT_BIDS.save(
+        deps.storage,
+        (auction_id, &sender),
+        &UnitBid {
+            price,
+            quantity,
+            seq: state.unit_bids,
+        },
+    )?;
+    state.unit_bids += 1;
+
+    resp = extend_end(&config, &mut state, &env, resp);
+
+    track_commission(deps.storage, auction_id, &sender, commission)?;
+
+    let commission = set_aside_keeper_share(&config, &mut state, commission);
+
+    if !commission.is_zero() {
+        resp = distribute_commission(deps.storage, auction_id, &config, commission, resp)?
+            .add_attribute("commission_payer", sender.as_str());
+    }
+
+    BIDS.save(deps.storage, (auction_id, &sender), &deposit)?;
+    STATES.save(deps.storage, auction_id, &state)?;
+
+    resp = resp
+        .add_attribute("action", "bid_units")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", sender.as_str())
+        .add_attribute("price", price)
+        .add_attribute("quantity", quantity.to_string());
+    Ok(resp)
+}
+
+// Store the sealed bid of `sender`, along with the `deposit` already
+// transferred to the contract
+fn place_commitment(
+    mut deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    sender: Addr,
+    deposit: Uint128,
+    hash: Binary,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let mut state = STATES.load(deps.storage, auction_id)?;
+
+    check_bidder(
+        deps.branch(),
+        &env,
+        auction_id,
+        &config,
+        &state,
+        &sender,
+        None,
+        None,
+    )?;
+
+    // Bids can only be committed to sealed auctions, before the commit end
+    match &config.kind {
+        AuctionKind::Sealed(sealed) if !sealed.commit_end.is_triggered(&env.block) => {}
+        _ => return Err(ContractError::InvalidPhase),
+    }
+
+    // Each bidder commits to a single bid
+    if COMMITMENTS.has(deps.storage, (auction_id, &sender)) {
+        return Err(ContractError::AlreadyCommitted);
+    }
+
+    COMMITMENTS.save(
+        deps.storage,
+        (auction_id, &sender),
+        &Commitment { hash, deposit },
+    )?;
+
+    state.unrevealed_deposits += deposit;
+    STATES.save(deps.storage, auction_id, &state)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "commit")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", sender.as_str())
+        .add_attribute("deposit", deposit);
+    Ok(resp)
+}
+
+pub fn reveal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    amount: Uint128,
+    salt: Binary,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let mut state = STATES.load(deps.storage, auction_id)?;
+
+    let mut resp = Response::new();
+
+    // If auction is already closed, then the reveal cannot be processed
+    if state.is_closed(&env.block) {
         return Err(ContractError::ClosedAcution);
     }
 
-    // Only the owner of the auction can close it
-    if owner != info.sender {
-        return Err(ContractError::Unauthorized {
-            owner: owner.to_string(),
+    // Bids are revealed between the commit end and the auction end
+    match &config.kind {
+        AuctionKind::Sealed(sealed) if sealed.commit_end.is_triggered(&env.block) => {}
+        _ => return Err(ContractError::InvalidPhase),
+    }
+
+    // The revealed bid must match the one committed by the sender
+    let commitment = match COMMITMENTS.may_load(deps.storage, (auction_id, &info.sender))? {
+        Some(commitment) => commitment,
+        None => return Err(ContractError::InvalidReveal),
+    };
+    if commitment.hash != commitment_hash(&info.sender, amount, &salt) {
+        return Err(ContractError::InvalidReveal);
+    }
+
+    // The deposit must cover the bid and its commission
+    let commission = config.commission.fee(amount);
+    if commitment.deposit < amount + commission {
+        return Err(ContractError::InsufficientDeposit {
+            deposit: commitment.deposit,
+            amount,
+            commission,
         });
     }
 
-    // If there exist a maximum bid, we should send it to the contract owner
-    if let Some(highest_bid) = state.highest_bid {
-        let funds: Vec<_> = coins(highest_bid.1.u128(), denom);
+    COMMITMENTS.remove(deps.storage, (auction_id, &info.sender));
+    state.unrevealed_deposits -= commitment.deposit;
 
-        // Create a bank message to send funds to the contract owner
-        let commission_msg = BankMsg::Send {
-            to_address: owner.into_string(),
-            amount: funds,
-        };
+    // The highest revealed bid leads, ties going to the first revealed one
+    if !amount.is_zero() {
+        state.record_bid(&info.sender, amount);
+    }
 
-        // Update the bid for the sender
-        BIDS.remove(deps.storage, &highest_bid.0);
+    track_commission(deps.storage, auction_id, &info.sender, commission)?;
 
+    let distributed = set_aside_keeper_share(&config, &mut state, commission);
+
+    if !distributed.is_zero() {
+        resp = distribute_commission(deps.storage, auction_id, &config, distributed, resp)?
+            .add_attribute("commission_payer", info.sender.as_str());
+    }
+
+    // The bid is kept like an open one, the rest of the deposit is refunded
+    if !amount.is_zero() {
+        BIDS.save(deps.storage, (auction_id, &info.sender), &amount)?;
+    }
+    let refund = commitment.deposit - amount - commission;
+    if !refund.is_zero() {
         resp = resp
-            .add_message(commission_msg)
-            .add_attribute("auction_collection", highest_bid.0.as_str());
+            .add_message(send_tokens(&config.denom, &info.sender, refund)?)
+            .add_attribute("refund", refund);
+    }
+
+    STATES.save(deps.storage, auction_id, &state)?;
+
+    resp = resp
+        .add_attribute("action", "reveal")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("amount", amount);
+    Ok(resp)
+}
+
+// Sell the lot of a Dutch auction to `sender` at `price`, refunding whatever
+// exceeds the price and its commission
+fn buy_at_price(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    config: &Config,
+    mut state: State,
+    sender: Addr,
+    funds: Uint128,
+    price: Uint128,
+) -> Result<Response, ContractError> {
+    // The commission is due on the price only, not on the refunded funds
+    let commission = config.commission.fee(price);
+    if funds < price + commission {
+        return Err(ContractError::BelowCurrentPrice {
+            funds,
+            price,
+            commission,
+        });
+    }
+
+    // The winning bid is paid to the proceeds recipient when closing
+    state.highest_bid = Some((sender.clone(), price));
+    let mut resp = close_auction(storage, auction_id, config, &mut state)?;
+
+    // No keeper is needed to settle the auction, so the whole commission goes
+    // to the commission recipients
+    if !commission.is_zero() {
+        resp = distribute_commission(storage, auction_id, config, commission, resp)?
+            .add_attribute("commission_payer", sender.as_str());
+    }
+
+    let refund = funds - price - commission;
+    if !refund.is_zero() {
+        resp = resp
+            .add_message(send_tokens(&config.denom, &sender, refund)?)
+            .add_attribute("refund", refund);
     }
 
     // Update the state to close the auction
-    STATE.update(deps.storage, |mut state| -> StdResult<_> {
-        state.current_status = Status::Closed;
-        Ok(state)
-    })?;
+    STATES.save(storage, auction_id, &state)?;
 
     resp = resp
-        .add_attribute("action", "close")
-        .add_attribute("sender", info.sender.as_str());
+        .add_attribute("action", "bid")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", sender.as_str())
+        .add_attribute("price", price);
     Ok(resp)
 }
 
-pub fn retract(
+pub fn close(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    recipient: Option<String>,
+    auction_id: u64,
 ) -> Result<Response, ContractError> {
-    let status = STATE.load(deps.storage)?.current_status;
-    let denom = CONFIG.load(deps.storage)?.denom;
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let mut state = STATES.load(deps.storage, auction_id)?;
 
-    let mut resp = Response::new();
+    // If auction is already closed, then the action cannot be processed
+    if state.is_final() {
+        return Err(ContractError::ClosedAcution);
+    }
 
-    // If auction is yet open, then the action cannot be processed
-    if status == Status::Open {
+    // Only the owner of the auction, or an operator allowed to, can close it
+    authorize(
+        deps.storage,
+        auction_id,
+        &config,
+        &info.sender,
+        Permission::Close,
+    )?;
+
+    // A sealed auction cannot be closed before all bids had time to be
+    // revealed
+    if matches!(config.kind, AuctionKind::Sealed(_)) && !state.has_ended(&env.block) {
         return Err(ContractError::OpenAcution);
     }
 
-    // If there is not any fund to retract, then the action cannot be processed
-    let funds = match BIDS.load(deps.storage, &info.sender) {
-        Ok(amount) => coins(amount.u128(), denom),
-        _ => return Err(ContractError::InvalidRetract),
-    };
+    let mut resp = close_auction(deps.storage, auction_id, &config, &mut state)?;
 
-    // Retrieve recipient or use the message sender as the default value
-    let recipient = match recipient {
-        Some(recipient) => deps.api.addr_validate(&recipient)?,
-        None => info.sender.clone(),
-    };
+    // Commissions set aside for the keeper reward are not needed anymore, so
+    // they go to the commission recipients
+    let pool = std::mem::take(&mut state.commission_pool);
+    if !pool.is_zero() {
+        resp = distribute_commission(deps.storage, auction_id, &config, pool, resp)?;
+    }
+
+    // Update the state to close the auction
+    STATES.save(deps.storage, auction_id, &state)?;
+
+    resp = resp
+        .add_attribute("action", "close")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str());
+    Ok(resp)
+}
 
-    // Create a bank message to send funds to the contract owner
-    let retract_msg = BankMsg::Send {
-        to_address: recipient.to_string(),
-        amount: funds,
+pub fn cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    reason: String,
+) -> Result<Response, ContractError> {
+    // Commissions are paid back with native funds, if the auction is priced in
+    // a native token
+    let funds = match CONFIGS.load(deps.storage, auction_id)?.denom {
+        Denom::Native(denom) => info
+            .funds
+            .iter()
+            .find(|coin| coin.denom == denom)
+            .map(|coin| coin.amount)
+            .unwrap_or_default(),
+        Denom::Cw20(_) => Uint128::zero(),
     };
 
-    // Remove the bid for the sender who is retracting right now
-    BIDS.remove(deps.storage, &recipient);
+    cancel_auction(deps, env, auction_id, info.sender, funds, reason)
+}
+
+// Cancel the auction on behalf of `sender`, along with `funds` tokens already
+// transferred to the contract to pay back the commissions. No bid is paid out
+// and every bidder can retract its whole bid.
+fn cancel_auction(
+    deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    sender: Addr,
+    funds: Uint128,
+    reason: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let mut state = STATES.load(deps.storage, auction_id)?;
+
+    let mut resp = Response::new();
+
+    // If auction is already closed (or its end has passed), then it cannot be
+    // cancelled anymore, as its winner is only waiting to be settled
+    if state.is_closed(&env.block) {
+        return Err(ContractError::ClosedAcution);
+    }
+
+    // Only the owner of the auction, or an operator allowed to, can cancel it
+    authorize(
+        deps.storage,
+        auction_id,
+        &config,
+        &sender,
+        Permission::Cancel,
+    )?;
+
+    let pool = std::mem::take(&mut state.commission_pool);
+    if config.refund_commissions {
+        // Commissions still held by the contract are refunded as they are,
+        // the owner pays back the ones already sent to the recipients
+        let accrued = ACCRUED_FEES
+            .prefix(auction_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        let paid = COMMISSIONS
+            .prefix(auction_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, paid)| paid))
+            .sum::<StdResult<Uint128>>()?;
+        let held = pool + accrued.iter().map(|(_, fees)| *fees).sum::<Uint128>();
+        let required = paid - held;
+        if funds != required {
+            return Err(ContractError::InvalidCancelFunds { required, funds });
+        }
+
+        for (recipient, _) in accrued {
+            ACCRUED_FEES.remove(deps.storage, (auction_id, &recipient));
+        }
+        resp = resp.add_attribute("commissions_refunded", paid);
+    } else {
+        if !funds.is_zero() {
+            return Err(ContractError::InvalidCancelFunds {
+                required: Uint128::zero(),
+                funds,
+            });
+        }
+
+        // Commissions set aside for the keeper reward go to the commission
+        // recipients, like on close
+        if !pool.is_zero() {
+            resp = distribute_commission(deps.storage, auction_id, &config, pool, resp)?;
+        }
+    }
+
+    // If the lot has been deposited, it goes back to the owner
+    if let (Some(lot), Status::Open) = (&config.lot, &state.current_status) {
+        resp = resp
+            .add_message(send_lot(lot, &config.owner)?)
+            .add_attribute("lot_recipient", config.owner.as_str());
+    }
+
+    state.current_status = Status::Cancelled;
+    STATES.save(deps.storage, auction_id, &state)?;
+
+    resp = resp
+        .add_attribute("action", "cancel")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", sender.as_str())
+        .add_attribute("reason", reason);
+    Ok(resp)
+}
+
+// Propose `new_owner` as the owner of the auction, who has to accept the
+// ownership before its expiry
+pub fn transfer_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    new_owner: String,
+    expiry: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let state = STATES.load(deps.storage, auction_id)?;
+
+    // Only the owner of the auction can transfer it
+    if !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {
+            owner: config.owner.to_string(),
+        });
+    }
+
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+    let expiry = expiry.unwrap_or_default();
+    if expiry.is_expired(&env.block) {
+        return Err(ContractError::OwnershipExpired);
+    }
+
+    // The owner cannot bid, so neither can the new one while bids are open
+    if !state.is_final() && has_bid(deps.storage, auction_id, &new_owner) {
+        return Err(ContractError::OwnerWithBids {
+            owner: new_owner.into_string(),
+        });
+    }
+
+    PENDING_OWNERS.save(
+        deps.storage,
+        auction_id,
+        &PendingOwner {
+            owner: new_owner.clone(),
+            expiry,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "transfer_ownership")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("pending_owner", new_owner.as_str())
+        .add_attribute("expiry", expiry.to_string());
+    Ok(resp)
+}
+
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIGS.load(deps.storage, auction_id)?;
+    let state = STATES.load(deps.storage, auction_id)?;
+
+    // Only the pending owner can accept the ownership, before its expiry
+    let pending = match PENDING_OWNERS.may_load(deps.storage, auction_id)? {
+        Some(pending) if pending.owner == info.sender => pending,
+        _ => return Err(ContractError::NoPendingOwnership),
+    };
+    if pending.expiry.is_expired(&env.block) {
+        return Err(ContractError::OwnershipExpired);
+    }
+
+    // Bids placed meanwhile by the new owner would compete with the auction
+    // it manages
+    if !state.is_final() && has_bid(deps.storage, auction_id, &info.sender) {
+        return Err(ContractError::OwnerWithBids {
+            owner: info.sender.into_string(),
+        });
+    }
+
+    let previous_owner = std::mem::replace(&mut config.owner, pending.owner);
+    CONFIGS.save(deps.storage, auction_id, &config)?;
+    PENDING_OWNERS.remove(deps.storage, auction_id);
+
+    // The operators were granted by the previous owner, the new one appoints
+    // its own
+    let operators = OPERATORS
+        .prefix(auction_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for operator in operators {
+        OPERATORS.remove(deps.storage, (auction_id, &operator));
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "accept_ownership")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("previous_owner", previous_owner.as_str());
+    Ok(resp)
+}
+
+// Give up the management of the auction, which can only be settled afterwards
+pub fn renounce_ownership(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIGS.load(deps.storage, auction_id)?;
+    let state = STATES.load(deps.storage, auction_id)?;
+
+    // Only the owner of the auction can renounce it
+    if !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {
+            owner: config.owner.to_string(),
+        });
+    }
+
+    // Without an end, nobody could close the auction anymore
+    if !state.is_final() && state.end.is_none() {
+        return Err(ContractError::InvalidRenounce);
+    }
+
+    config.renounced = true;
+    CONFIGS.save(deps.storage, auction_id, &config)?;
+    PENDING_OWNERS.remove(deps.storage, auction_id);
+
+    let resp = Response::new()
+        .add_attribute("action", "renounce_ownership")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str());
+    Ok(resp)
+}
+
+// Grant `permissions` to `operator`, replacing the ones it already has
+pub fn add_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+    operator: String,
+    permissions: Vec<Permission>,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let state = STATES.load(deps.storage, auction_id)?;
+
+    // Only the owner of the auction can manage its operators
+    if !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {
+            owner: config.owner.to_string(),
+        });
+    }
+
+    if permissions.is_empty() {
+        return Err(ContractError::EmptyPermissions);
+    }
+
+    // Operators cannot bid, so they cannot have bids while the auction is
+    // open
+    let operator = deps.api.addr_validate(&operator)?;
+    if !state.is_final() && has_bid(deps.storage, auction_id, &operator) {
+        return Err(ContractError::OperatorWithBids {
+            operator: operator.into_string(),
+        });
+    }
+
+    let report = permissions
+        .iter()
+        .map(|permission| format!("{permission:?}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    OPERATORS.save(deps.storage, (auction_id, &operator), &permissions)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "add_operator")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("operator", operator.as_str())
+        .add_attribute("permissions", report);
+    Ok(resp)
+}
+
+pub fn remove_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+
+    // Only the owner of the auction can manage its operators
+    if !config.is_owner(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {
+            owner: config.owner.to_string(),
+        });
+    }
+
+    let operator = deps.api.addr_validate(&operator)?;
+    if !OPERATORS.has(deps.storage, (auction_id, &operator)) {
+        return Err(ContractError::NotOperator {
+            address: operator.into_string(),
+        });
+    }
+    OPERATORS.remove(deps.storage, (auction_id, &operator));
+
+    let resp = Response::new()
+        .add_attribute("action", "remove_operator")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("operator", operator.as_str());
+    Ok(resp)
+}
+
+pub fn update_metadata(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+    description: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIGS.load(deps.storage, auction_id)?;
+
+    // Only the owner of the auction, or an operator allowed to, can update it
+    authorize(
+        deps.storage,
+        auction_id,
+        &config,
+        &info.sender,
+        Permission::UpdateMetadata,
+    )?;
+
+    config.description = description;
+    CONFIGS.save(deps.storage, auction_id, &config)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "update_metadata")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str());
+    Ok(resp)
+}
+
+// Add and remove members of the access list. Bids already placed are kept
+// when their bidder is not allowed anymore.
+pub fn update_access_list(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+
+    // Only the owner of the auction, or an operator allowed to, can manage its
+    // access list
+    authorize(
+        deps.storage,
+        auction_id,
+        &config,
+        &info.sender,
+        Permission::ManageAccess,
+    )?;
+
+    for address in &add {
+        let address = deps.api.addr_validate(address)?;
+        ACCESS_LIST.save(deps.storage, (auction_id, &address), &())?;
+    }
+    for address in &remove {
+        let address = deps.api.addr_validate(address)?;
+        ACCESS_LIST.remove(deps.storage, (auction_id, &address));
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "update_access_list")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string());
+    Ok(resp)
+}
+
+// Replace the Merkle root of the eligible bidders, or remove it to let anyone
+// bid. Bidders verified against a previous root have to prove their
+// eligibility again.
+pub fn set_merkle_root(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+    merkle_root: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIGS.load(deps.storage, auction_id)?;
+
+    // Only the owner of the auction, or an operator allowed to manage who can
+    // bid, can change its Merkle root
+    authorize(
+        deps.storage,
+        auction_id,
+        &config,
+        &info.sender,
+        Permission::ManageAccess,
+    )?;
+
+    if let Some(merkle_root) = &merkle_root {
+        check_merkle_root(merkle_root, &config.kind, config.quantity)?;
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "set_merkle_root")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute(
+            "merkle_root",
+            merkle_root
+                .as_ref()
+                .map_or_else(|| "none".to_string(), Binary::to_base64),
+        );
+
+    config.merkle_root = merkle_root;
+    CONFIGS.save(deps.storage, auction_id, &config)?;
+
+    Ok(resp)
+}
+
+// A Merkle root is a sha256 hash, and only gates plain bids since they are the
+// ones carrying a proof
+fn check_merkle_root(
+    merkle_root: &Binary,
+    kind: &AuctionKind,
+    quantity: Option<u64>,
+) -> Result<(), ContractError> {
+    if merkle_root.len() != 32 || matches!(kind, AuctionKind::Sealed(_)) || quantity.is_some() {
+        return Err(ContractError::InvalidMerkleRoot);
+    }
+    Ok(())
+}
+
+// Check that `bidder` is a leaf of the Merkle tree of the auction, if any. The
+// first bid of each address carries a proof, later bids rely on the address
+// being verified against the current root.
+fn check_eligibility(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    config: &Config,
+    bidder: &Addr,
+    proof: Option<Vec<Binary>>,
+) -> Result<(), ContractError> {
+    let Some(merkle_root) = &config.merkle_root else {
+        return Ok(());
+    };
+    if VERIFIED.may_load(storage, (auction_id, bidder))?.as_ref() == Some(merkle_root) {
+        return Ok(());
+    }
+
+    match proof {
+        Some(proof) if verify_merkle_proof(merkle_root, bidder, &proof) => {
+            VERIFIED.save(storage, (auction_id, bidder), merkle_root)?;
+            Ok(())
+        }
+        _ => Err(ContractError::NotEligible {
+            bidder: bidder.to_string(),
+        }),
+    }
+}
+
+// Check that `bidder` can bid on the auction right now: the auction must be
+// open and started, the bidder cannot be its owner nor one of its operators,
+// and it must meet every eligibility requirement of the auction. The Merkle
+// `proof` and the gating `token_id` are only carried by plain bids.
+#[allow(clippy::too_many_arguments)]
+fn check_bidder(
+    deps: DepsMut,
+    env: &Env,
+    auction_id: u64,
+    config: &Config,
+    state: &State,
+    bidder: &Addr,
+    proof: Option<Vec<Binary>>,
+    token_id: Option<String>,
+) -> Result<(), ContractError> {
+    // If auction is already closed (or its end has passed), then bid cannot be
+    // processed
+    if state.is_closed(&env.block) {
+        return Err(ContractError::ClosedAcution);
+    }
+
+    // If auction lot has not been deposited yet, then bid cannot be processed
+    if state.current_status == Status::Pending {
+        return Err(ContractError::PendingAuction);
+    }
+
+    // If auction has not reached its start yet, then bid cannot be processed
+    if let Some(start) = config.start.filter(|_| !config.has_started(&env.block)) {
+        return Err(ContractError::NotStartedAuction { start });
+    }
+
+    // Owner of the auction cannot bid
+    if config.owner == *bidder {
+        return Err(ContractError::InvalidBid {
+            owner: config.owner.to_string(),
+        });
+    }
+
+    // Operators of the auction cannot bid either
+    if OPERATORS.has(deps.storage, (auction_id, bidder)) {
+        return Err(ContractError::OperatorBid {
+            operator: bidder.to_string(),
+        });
+    }
+
+    // Only bidders allowed by the access list can bid
+    check_access(deps.storage, auction_id, config, bidder)?;
+
+    // Only members of the group of the auction can bid
+    check_group(&deps.querier, config, bidder)?;
+
+    // Only holders of the gating collection can bid
+    check_nft_holder(&deps.querier, config, bidder, token_id)?;
+
+    // Only bidders in the Merkle tree of the auction can bid
+    check_eligibility(deps.storage, auction_id, config, bidder, proof)
+}
+
+// Check that `bidder` is a member of the cw4 group of the auction, if any,
+// with at least its minimum weight
+fn check_group(
+    querier: &QuerierWrapper,
+    config: &Config,
+    bidder: &Addr,
+) -> Result<(), ContractError> {
+    let Some(gate) = &config.group else {
+        return Ok(());
+    };
+
+    let member: MemberResponse = querier.query_wasm_smart(
+        &gate.group,
+        &Cw4QueryMsg::Member {
+            addr: bidder.to_string(),
+            at_height: None,
+        },
+    )?;
+    if member.weight.is_none_or(|weight| weight < gate.min_weight) {
+        return Err(ContractError::NotGroupMember {
+            bidder: bidder.to_string(),
+            min_weight: gate.min_weight,
+        });
+    }
+    Ok(())
+}
+
+// Check that `bidder` holds a token of the gating collection of the auction,
+// if any. The given token is checked directly, otherwise the tokens of the
+// bidder are looked up.
+fn check_nft_holder(
+    querier: &QuerierWrapper,
+    config: &Config,
+    bidder: &Addr,
+    token_id: Option<String>,
+) -> Result<(), ContractError> {
+    let Some(collection) = &config.nft_gate else {
+        return Ok(());
+    };
+
+    let is_holder = match token_id {
+        // A token missing from the collection is not held by the bidder either
+        Some(token_id) => querier
+            .query_wasm_smart::<OwnerOfResponse>(
+                collection,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id,
+                    include_expired: None,
+                },
+            )
+            .is_ok_and(|resp| resp.owner == *bidder),
+        None => {
+            let resp: TokensResponse = querier.query_wasm_smart(
+                collection,
+                &Cw721QueryMsg::Tokens {
+                    owner: bidder.to_string(),
+                    start_after: None,
+                    limit: Some(1),
+                },
+            )?;
+            !resp.tokens.is_empty()
+        }
+    };
+    if !is_holder {
+        return Err(ContractError::NotNftHolder {
+            bidder: bidder.to_string(),
+            collection: collection.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// Check that `bidder` is allowed to bid by the access list of the auction
+fn check_access(
+    storage: &dyn Storage,
+    auction_id: u64,
+    config: &Config,
+    bidder: &Addr,
+) -> Result<(), ContractError> {
+    let is_allowed = match config.access {
+        AccessControl::Open => true,
+        AccessControl::Allowlist => ACCESS_LIST.has(storage, (auction_id, bidder)),
+        AccessControl::Denylist => !ACCESS_LIST.has(storage, (auction_id, bidder)),
+    };
+    if !is_allowed {
+        return Err(ContractError::BidderNotAllowed {
+            bidder: bidder.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// Check that `sender` is the owner of the auction, or an operator granted
+// `permission`. Operators act on behalf of the owner, so they cannot act
+// anymore once the ownership has been renounced.
+fn authorize(
+    storage: &dyn Storage,
+    auction_id: u64,
+    config: &Config,
+    sender: &Addr,
+    permission: Permission,
+) -> Result<(), ContractError> {
+    let is_operator = !config.renounced
+        && OPERATORS
+            .may_load(storage, (auction_id, sender))?
+            .is_some_and(|permissions| permissions.contains(&permission));
+    if !config.is_owner(sender.as_str()) && !is_operator {
+        return Err(ContractError::Unauthorized {
+            owner: config.owner.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// Whether `address` takes part in the auction with a bid, open or sealed
+fn has_bid(storage: &dyn Storage, auction_id: u64, address: &Addr) -> bool {
+    BIDS.has(storage, (auction_id, address)) || COMMITMENTS.has(storage, (auction_id, address))
+}
+
+pub fn settle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let mut state = STATES.load(deps.storage, auction_id)?;
+
+    // If auction is already closed, then the action cannot be processed
+    if state.is_final() {
+        return Err(ContractError::ClosedAcution);
+    }
+
+    // Anyone can settle the auction, but only once its end has passed
+    if !state.has_ended(&env.block) {
+        return Err(ContractError::OpenAcution);
+    }
+
+    let mut resp = close_auction(deps.storage, auction_id, &config, &mut state)?;
+
+    // The commissions set aside are the reward for whoever settles the auction
+    let reward = std::mem::take(&mut state.commission_pool);
+    if !reward.is_zero() {
+        resp = resp
+            .add_message(send_tokens(&config.denom, &info.sender, reward)?)
+            .add_attribute("keeper_reward", reward);
+    }
+
+    // Update the state to close the auction
+    STATES.save(deps.storage, auction_id, &state)?;
+
+    resp = resp
+        .add_attribute("action", "settle")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str());
+    Ok(resp)
+}
+
+// Send the highest bid (if any and if it meets the reserve price), or the
+// payments for the units allocated by a multi-unit auction, to the proceeds
+// recipient, deliver the escrowed lot to the winner (or back to the owner
+// without a winner) and mark the auction as closed. The caller is responsible
+// for saving the updated state.
+fn close_auction(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    config: &Config,
+    state: &mut State,
+) -> Result<Response, ContractError> {
+    let mut resp = Response::new();
+
+    // If there exist a maximum bid, we should send its clearing price to the
+    // proceeds recipient
+    if let (Some(highest_bid), Some(price)) = (state.winner(config), state.clearing_price(config)) {
+        // Create a message to send funds to the proceeds recipient
+        let commission_msg = send_tokens(&config.denom, config.proceeds_recipient(), price)?;
+
+        // Update the bid for the sender, whatever exceeds the clearing price
+        // stays refundable through retract
+        let remaining = highest_bid.1 - price;
+        if remaining.is_zero() {
+            BIDS.remove(storage, (auction_id, &highest_bid.0));
+        } else {
+            BIDS.save(storage, (auction_id, &highest_bid.0), &remaining)?;
+        }
+
+        resp = resp
+            .add_message(commission_msg)
+            .add_attribute("auction_collection", highest_bid.0.as_str())
+            .add_attribute("clearing_price", price);
+    } else if state.highest_bid.is_some() {
+        // The highest bid stays refundable through retract
+        resp = resp.add_attribute("outcome", "reserve_not_met");
+    }
+
+    // The units of a multi-unit auction are paid at the clearing price by
+    // each winning bidder, the rest of the deposits stays refundable through
+    // retract
+    let allocation = match config.quantity {
+        Some(_) => allocate(storage, auction_id, config)?,
+        None => (None, vec![]),
+    };
+    if let (Some(price), allocations) = allocation {
+        let mut proceeds = Uint128::zero();
+        for (bidder, units) in &allocations {
+            let payment = price * Uint128::from(*units);
+            let remaining = BIDS.load(storage, (auction_id, bidder))? - payment;
+            if remaining.is_zero() {
+                BIDS.remove(storage, (auction_id, bidder));
+            } else {
+                BIDS.save(storage, (auction_id, bidder), &remaining)?;
+            }
+            proceeds += payment;
+        }
+        let units_sold: u64 = allocations.iter().map(|(_, units)| units).sum();
+
+        resp = resp
+            .add_message(send_tokens(
+                &config.denom,
+                config.proceeds_recipient(),
+                proceeds,
+            )?)
+            .add_attribute("clearing_price", price)
+            .add_attribute("units_sold", units_sold.to_string());
+    }
+
+    // If the lot has been deposited, it goes to the winner or back to the owner
+    if let (Some(lot), Status::Open) = (&config.lot, &state.current_status) {
+        let recipient = match state.winner(config) {
+            Some((winner, _)) => winner,
+            None => &config.owner,
+        };
+        resp = resp
+            .add_message(send_lot(lot, recipient)?)
+            .add_attribute("lot_recipient", recipient.as_str());
+    }
+
+    // Deposits of sealed bids never revealed may go to the proceeds recipient
+    if let AuctionKind::Sealed(SealedAuction {
+        unrevealed: UnrevealedDeposits::Forfeit,
+        ..
+    }) = &config.kind
+    {
+        let forfeited = std::mem::take(&mut state.unrevealed_deposits);
+        if !forfeited.is_zero() {
+            resp = resp
+                .add_message(send_tokens(
+                    &config.denom,
+                    config.proceeds_recipient(),
+                    forfeited,
+                )?)
+                .add_attribute("forfeited_deposits", forfeited);
+        }
+    }
+
+    state.current_status = Status::Closed;
+
+    Ok(resp)
+}
+
+// Build the message transferring `amount` tokens of the bidding currency
+fn send_tokens(denom: &Denom, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), denom),
+        }
+        .into(),
+        Denom::Cw20(token) => WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+
+    Ok(msg)
+}
+
+// Build the message transferring the escrowed lot
+fn send_lot(lot: &Lot, recipient: &Addr) -> StdResult<CosmosMsg> {
+    let msg = match lot {
+        Lot::Native(amount) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: amount.clone(),
+        }
+        .into(),
+        Lot::Cw20 { token, amount } => WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: *amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+        Lot::Nft {
+            collection,
+            token_id,
+        } => WasmMsg::Execute {
+            contract_addr: collection.to_string(),
+            msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: token_id.clone(),
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+
+    Ok(msg)
+}
+
+// Send the commission to its recipients, or accrue it for them to claim later,
+// reporting the split in the response
+fn distribute_commission(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    config: &Config,
+    amount: Uint128,
+    mut resp: Response,
+) -> StdResult<Response> {
+    let split = config.commission_split(amount);
+
+    let report = split
+        .iter()
+        .map(|(recipient, amount)| format!("{recipient}:{amount}"))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    for (recipient, amount) in split.into_iter().filter(|(_, amount)| !amount.is_zero()) {
+        match config.commission_mode {
+            CommissionMode::Push => {
+                resp = resp.add_message(send_tokens(&config.denom, &recipient, amount)?);
+            }
+            CommissionMode::Accrue => {
+                ACCRUED_FEES.update(storage, (auction_id, &recipient), |fees| -> StdResult<_> {
+                    Ok(fees.unwrap_or_default() + amount)
+                })?;
+            }
+        }
+    }
+
+    Ok(resp.add_attribute("commission_split", report))
+}
+
+pub fn claim_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let denom = CONFIGS.load(deps.storage, auction_id)?.denom;
+
+    // If there is not any fee accrued for the sender, then the action cannot
+    // be processed
+    let fees = match ACCRUED_FEES.may_load(deps.storage, (auction_id, &info.sender))? {
+        Some(fees) if !fees.is_zero() => fees,
+        _ => return Err(ContractError::NoAccruedFees),
+    };
+
+    ACCRUED_FEES.remove(deps.storage, (auction_id, &info.sender));
+
+    let resp = Response::new()
+        .add_message(send_tokens(&denom, &info.sender, fees)?)
+        .add_attribute("action", "claim_fees")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("amount", fees);
+    Ok(resp)
+}
+
+pub fn retract(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let mut state = STATES.load(deps.storage, auction_id)?;
+    let denom = config.denom.clone();
+
+    let mut resp = Response::new();
+
+    // If auction is yet open, then the action cannot be processed
+    if !state.is_closed(&env.block) {
+        return Err(ContractError::OpenAcution);
+    }
+
+    // If the auction ended by time but has not been closed yet, the winning
+    // bid is still stored and it must not be retracted
+    let is_winner = match config.quantity {
+        Some(_) => allocate(deps.storage, auction_id, &config)?
+            .1
+            .iter()
+            .any(|(bidder, _)| *bidder == info.sender),
+        None => matches!(state.winner(&config), Some((winner, _)) if *winner == info.sender),
+    };
+    if state.current_status == Status::Open && is_winner {
+        return Err(ContractError::InvalidRetract);
+    }
+
+    // Deposits of sealed bids never revealed are retracted along with the
+    // bid, unless they are forfeited by an auction not cancelled
+    let is_cancelled = state.current_status == Status::Cancelled;
+    let bid = BIDS
+        .may_load(deps.storage, (auction_id, &info.sender))?
+        .unwrap_or_default();
+    let commitment = COMMITMENTS.may_load(deps.storage, (auction_id, &info.sender))?;
+    let deposit = match (&config.kind, commitment) {
+        (AuctionKind::Sealed(SealedAuction { unrevealed, .. }), Some(commitment))
+            if is_cancelled || *unrevealed == UnrevealedDeposits::Refund =>
+        {
+            commitment.deposit
+        }
+        _ => Uint128::zero(),
+    };
+
+    // Commissions paid by the sender are retracted as well if the auction has
+    // been cancelled, when they are refundable
+    let commission = match is_cancelled && config.refund_commissions {
+        true => COMMISSIONS
+            .may_load(deps.storage, (auction_id, &info.sender))?
+            .unwrap_or_default(),
+        false => Uint128::zero(),
+    };
+
+    // If there is not any fund to retract, then the action cannot be processed
+    let funds = bid + deposit + commission;
+    if funds.is_zero() {
+        return Err(ContractError::InvalidRetract);
+    }
+
+    // Retrieve recipient or use the message sender as the default value
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+
+    // Create a message to send funds to the recipient
+    let retract_msg = send_tokens(&denom, &recipient, funds)?;
+
+    // Remove the bid for the sender who is retracting right now
+    BIDS.remove(deps.storage, (auction_id, &info.sender));
+    if !deposit.is_zero() {
+        COMMITMENTS.remove(deps.storage, (auction_id, &info.sender));
+        state.unrevealed_deposits -= deposit;
+        STATES.save(deps.storage, auction_id, &state)?;
+    }
+    if !commission.is_zero() {
+        COMMISSIONS.remove(deps.storage, (auction_id, &info.sender));
+    }
 
     resp = resp
         .add_message(retract_msg)
         .add_attribute("retracting_recipient", recipient.as_str())
         .add_attribute("action", "retract")
+        .add_attribute("auction_id", auction_id.to_string())
         .add_attribute("sender", info.sender.as_str());
     Ok(resp)
 }
@@ -0,0 +1,79 @@
+#[cfg(all(feature = "factory", not(feature = "library")))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+};
+use cw2::set_contract_version;
+
+use crate::error::ContractError;
+use crate::factory::exec::{self, INSTANTIATE_REPLY_ID};
+use crate::factory::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::factory::query;
+use crate::factory::state::{Config, AUCTION_COUNT, CONFIG};
+
+// version info for migration info
+const CONTRACT_NAME: &str = concat!(env!("CARGO_PKG_NAME"), "-factory");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(all(feature = "factory", not(feature = "library")), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            code_id: msg.code_id,
+        },
+    )?;
+    AUCTION_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(all(feature = "factory", not(feature = "library")), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateAuction(msg) => exec::create_auction(deps, info, *msg),
+    }
+}
+
+#[cfg_attr(all(feature = "factory", not(feature = "library")), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, ContractError> {
+    match reply.id {
+        INSTANTIATE_REPLY_ID => exec::register_auction(deps, env, reply),
+        id => Err(StdError::generic_err(format!("Unknown reply id: {id}")).into()),
+    }
+}
+
+#[cfg_attr(all(feature = "factory", not(feature = "library")), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query::config(deps)?),
+        QueryMsg::Auctions {
+            owner,
+            status,
+            created_after,
+            created_before,
+            start_after,
+            limit,
+        } => to_binary(&query::auctions(
+            deps,
+            owner,
+            status,
+            created_after,
+            created_before,
+            start_after,
+            limit,
+        )?),
+    }
+}
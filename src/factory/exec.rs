@@ -0,0 +1,81 @@
+use cosmwasm_std::{to_binary, DepsMut, Env, MessageInfo, Reply, Response, SubMsg, WasmMsg};
+use cw_utils::parse_reply_instantiate_data;
+
+use crate::{
+    factory::state::{auction_records, AuctionRecord, AUCTION_COUNT, CONFIG, PENDING_OWNER},
+    msg::InstantiateMsg,
+    ContractError,
+};
+
+// Id of the reply to the instantiation of a new auction
+pub const INSTANTIATE_REPLY_ID: u64 = 1;
+
+pub fn create_auction(
+    deps: DepsMut,
+    info: MessageInfo,
+    mut msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // The factory would otherwise be the owner of the auction, so it falls
+    // back to the sender
+    let owner = match &msg.owner {
+        Some(owner) => deps.api.addr_validate(owner)?,
+        None => info.sender.clone(),
+    };
+    msg.owner = Some(owner.to_string());
+
+    // The owner is kept until the instantiation replies with the address
+    PENDING_OWNER.save(deps.storage, &owner)?;
+
+    // The description is unbounded, so the label uses the id the auction is
+    // registered under once the instantiation replies
+    let id = AUCTION_COUNT.may_load(deps.storage)?.unwrap_or_default();
+
+    // Funds are forwarded, as they may carry a native lot
+    let instantiate_msg = WasmMsg::Instantiate {
+        admin: None,
+        code_id: config.code_id,
+        msg: to_binary(&msg)?,
+        funds: info.funds,
+        label: format!("bidwasm auction #{id}"),
+    };
+
+    let resp = Response::new()
+        .add_submessage(SubMsg::reply_on_success(
+            instantiate_msg,
+            INSTANTIATE_REPLY_ID,
+        ))
+        .add_attribute("action", "create_auction")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("owner", owner.as_str());
+    Ok(resp)
+}
+
+// Register the auction just instantiated
+pub fn register_auction(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, ContractError> {
+    let data = parse_reply_instantiate_data(reply)?;
+    let address = deps.api.addr_validate(&data.contract_address)?;
+
+    let owner = PENDING_OWNER.load(deps.storage)?;
+    PENDING_OWNER.remove(deps.storage);
+
+    let id = AUCTION_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    AUCTION_COUNT.save(deps.storage, &(id + 1))?;
+
+    auction_records().save(
+        deps.storage,
+        id,
+        &AuctionRecord {
+            address: address.clone(),
+            owner,
+            created_at: env.block.time,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "register_auction")
+        .add_attribute("auction_id", id.to_string())
+        .add_attribute("auction_address", address.as_str());
+    Ok(resp)
+}
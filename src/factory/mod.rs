@@ -0,0 +1,5 @@
+pub mod contract;
+pub mod exec;
+pub mod msg;
+pub mod query;
+pub mod state;
@@ -0,0 +1,60 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Timestamp};
+
+use crate::state::Status;
+
+// Instantiate message contains the code id of the bidwasm contract
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub code_id: u64,
+}
+
+// Executing the actions in the factory
+#[cw_serde]
+pub enum ExecuteMsg {
+    // instantiate a new bidwasm contract, owned by the sender if the message
+    // does not specify an owner
+    CreateAuction(Box<crate::msg::InstantiateMsg>),
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResp)]
+    Config {},
+    // Auctions created through the factory. Only the auction each contract is
+    // instantiated with is registered, not the ones created through it later
+    #[returns(AuctionsResp)]
+    Auctions {
+        owner: Option<String>,
+        status: Option<Status>,
+        created_after: Option<Timestamp>,
+        created_before: Option<Timestamp>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct ConfigResp {
+    pub code_id: u64,
+}
+
+#[cw_serde]
+pub struct AuctionRecordResp {
+    pub id: u64,
+    pub address: Addr,
+    pub owner: Addr,
+    pub created_at: Timestamp,
+    // status of the auction the contract was instantiated with, as it reports
+    // it
+    pub status: Status,
+}
+
+#[cw_serde]
+pub struct AuctionsResp {
+    pub auctions: Vec<AuctionRecordResp>,
+    // id to resume the scan after, if the page stopped before reading every
+    // record
+    pub next_start_after: Option<u64>,
+}
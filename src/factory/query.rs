@@ -0,0 +1,109 @@
+use cosmwasm_std::{Deps, Order, StdError, StdResult, Timestamp};
+use cw_storage_plus::Bound;
+
+use crate::{
+    factory::{
+        msg::{AuctionRecordResp, AuctionsResp, ConfigResp},
+        state::{auction_records, AuctionRecord, CONFIG},
+    },
+    msg::{ListAuctionsResp, QueryMsg},
+    state::Status,
+};
+
+// Pagination limits for the auction registry
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+// Records read at most by a page, whatever the filters leave of them
+const MAX_SCAN: usize = 100;
+
+pub fn config(deps: Deps) -> StdResult<ConfigResp> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResp {
+        code_id: config.code_id,
+    })
+}
+
+pub fn auctions(
+    deps: Deps,
+    owner: Option<String>,
+    status: Option<Status>,
+    created_after: Option<Timestamp>,
+    created_before: Option<Timestamp>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<AuctionsResp> {
+    let owner = owner
+        .map(|owner| deps.api.addr_validate(&owner))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    // The owner filter goes through its index, so only the auctions of the
+    // owner are read
+    let records: Box<dyn Iterator<Item = StdResult<(u64, AuctionRecord)>>> = match &owner {
+        Some(owner) => Box::new(auction_records().idx.owner.prefix(owner.clone()).range(
+            deps.storage,
+            start,
+            None,
+            Order::Ascending,
+        )),
+        None => Box::new(auction_records().range(deps.storage, start, None, Order::Ascending)),
+    };
+
+    // Auctions are registered in creation order, so the scan stops at the
+    // first one created too late. Otherwise it reads a bounded number of
+    // records, and the page tells where to resume.
+    let mut auctions = vec![];
+    let mut last_read = start_after;
+    let mut next_start_after = None;
+    for (scanned, item) in records.enumerate() {
+        let (id, record) = item?;
+        if created_before.is_some_and(|before| record.created_at >= before) {
+            break;
+        }
+        if auctions.len() == limit || scanned == MAX_SCAN {
+            next_start_after = last_read;
+            break;
+        }
+        last_read = Some(id);
+
+        if created_after.is_some_and(|after| record.created_at <= after) {
+            continue;
+        }
+
+        // Each contract is instantiated with a single auction, the only one
+        // the registry tracks, whose status the contract reports
+        let resp: ListAuctionsResp = deps.querier.query_wasm_smart(
+            &record.address,
+            &QueryMsg::ListAuctions {
+                status: None,
+                start_after: None,
+                limit: Some(1),
+            },
+        )?;
+        let auction_status = match resp.auctions.into_iter().next() {
+            Some(auction) => auction.status,
+            None => return Err(StdError::not_found("The contract has not any auction")),
+        };
+        if status
+            .as_ref()
+            .is_some_and(|status| *status != auction_status)
+        {
+            continue;
+        }
+
+        auctions.push(AuctionRecordResp {
+            id,
+            address: record.address,
+            owner: record.owner,
+            created_at: record.created_at,
+            status: auction_status,
+        });
+    }
+
+    Ok(AuctionsResp {
+        auctions,
+        next_start_after,
+    })
+}
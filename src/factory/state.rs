@@ -0,0 +1,51 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+
+// Factory Configuration information
+#[cw_serde]
+pub struct Config {
+    // code id of the bidwasm contract used for new auctions
+    pub code_id: u64,
+}
+
+// Auction instantiated by the factory
+#[cw_serde]
+pub struct AuctionRecord {
+    // address of the bidwasm contract
+    pub address: Addr,
+    // owner of the auction
+    pub owner: Addr,
+    // block time of the instantiation
+    pub created_at: Timestamp,
+}
+
+pub const CONFIG: Item<Config> = Item::new("factory_config");
+// number of auctions registered, which is also the id of the next one
+pub const AUCTION_COUNT: Item<u64> = Item::new("factory_auction_count");
+// owner of the auction being instantiated, until the instantiation replies
+pub const PENDING_OWNER: Item<Addr> = Item::new("factory_pending_owner");
+
+// Indexes of the auctions registered, by owner
+pub struct AuctionIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, AuctionRecord, u64>,
+}
+
+impl<'a> IndexList<AuctionRecord> for AuctionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<AuctionRecord>> + '_> {
+        let v: Vec<&dyn Index<AuctionRecord>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+// Auctions registered by the factory, keyed by id
+pub fn auction_records<'a>() -> IndexedMap<'a, u64, AuctionRecord, AuctionIndexes<'a>> {
+    let indexes = AuctionIndexes {
+        owner: MultiIndex::new(
+            |_, record| record.owner.clone(),
+            "factory_auctions",
+            "factory_auctions__owner",
+        ),
+    };
+    IndexedMap::new("factory_auctions", indexes)
+}
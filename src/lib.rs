@@ -1,6 +1,7 @@
 pub mod contract;
 mod error;
 pub mod exec;
+pub mod factory;
 pub mod msg;
 pub mod query;
 pub mod state;
@@ -1,37 +1,252 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Uint128};
+use cw20::{Cw20ReceiveMsg, UncheckedDenom};
+use cw721::Cw721ReceiveMsg;
+use cw_utils::{Expiration, Scheduled};
 
-// Instantiate message contains information about the auction itself:
-// - denom for the token bids
+use crate::state::{
+    AccessControl, AuctionKind, CommissionMode, CommissionPolicy, Commitment, GroupGate, Increment,
+    Lot, Permission, Pricing, SoftClose, Status,
+};
+
+// Instantiate message contains information about the first auction of the
+// contract, further ones are created with the same fields through
+// `ExecuteMsg::CreateAuction`:
+// - denom for the token bids, either native or CW20
 // - owner address for the auction management
 // - description for the auction
+// - optional start and end of the bidding window (block height or time)
+// - optional reward, taken from commissions, for who settles the auction
+// - optional soft close, extending a time based end on late bids
+// - optional reserve price, below which the auction has no winner
+// - optional starting price and minimum increment for the bids
+// - optional commission recipients (with shares summing to 1) and recipient
+// of the winning bid, both defaulting to the owner
+// - optional commission mode, pushing commissions on each bid by default
+// - optional on-chain lot, the auction opens once it is deposited (native
+// coins are sent along with the instantiation)
+// - optional auction kind, an ascending price (English) auction by default
+// - optional pricing, the winner pays its own bid by default
+// - optional quantity of identical units on sale, making bids carry a
+// per-unit price (reserve and starting price are per unit as well)
+// - optional buy-it-now price, closing the auction once a total bid reaches it
+// - optional refund of the commissions if the auction is cancelled, paid back
+// by the owner when cancelling
+// - optional access control, anyone can bid by default
+// - optional Merkle root of the addresses eligible to bid, checked on their
+// first bid
+// - optional cw4 group whose members, above a minimum weight, are the only
+// ones allowed to bid
+// - optional CW721 collection, only the holders of one of its tokens can bid
 #[cw_serde]
 pub struct InstantiateMsg {
-    pub denom: String,
+    pub denom: UncheckedDenom,
     pub owner: Option<String>,
     pub description: String,
-    pub commission: Option<u128>,
+    pub commission: Option<CommissionPolicy>,
+    pub start: Option<Scheduled>,
+    pub end: Option<Scheduled>,
+    pub keeper_reward: Option<Uint128>,
+    pub soft_close: Option<SoftClose>,
+    pub reserve_price: Option<Uint128>,
+    pub starting_price: Option<Uint128>,
+    pub min_increment: Option<Increment>,
+    pub commission_recipients: Option<Vec<(String, Decimal)>>,
+    pub proceeds_recipient: Option<String>,
+    pub commission_mode: Option<CommissionMode>,
+    pub lot: Option<UncheckedLot>,
+    pub kind: Option<AuctionKind>,
+    pub pricing: Option<Pricing>,
+    pub quantity: Option<u64>,
+    pub buy_now_price: Option<Uint128>,
+    pub refund_commissions: Option<bool>,
+    pub access: Option<AccessControl>,
+    pub merkle_root: Option<Binary>,
+    pub group: Option<UncheckedGroupGate>,
+    pub nft_gate: Option<String>,
+}
+
+// External cw4 group gating the bids, with an unvalidated address
+#[cw_serde]
+pub struct UncheckedGroupGate {
+    pub group: String,
+    pub min_weight: u64,
+}
+
+// On-chain asset sold by the auction, with unvalidated addresses
+#[cw_serde]
+pub enum UncheckedLot {
+    Native(Vec<Coin>),
+    Cw20 {
+        token: String,
+        amount: Uint128,
+    },
+    Nft {
+        collection: String,
+        token_id: String,
+    },
 }
 
 // Executing the actions in the smart contract
 #[cw_serde]
 pub enum ExecuteMsg {
-    Bid {},
-    Close {},
-    Retract { recipient: Option<String> },
+    CreateAuction(Box<InstantiateMsg>),
+    Bid {
+        auction_id: u64,
+        // Merkle proof of eligibility, only needed on the first bid
+        proof: Option<Vec<Binary>>,
+        // token held in the gating collection, looked up if missing
+        token_id: Option<String>,
+    },
+    BidUnits {
+        auction_id: u64,
+        price: Uint128,
+        quantity: u64,
+    },
+    BuyNow {
+        auction_id: u64,
+        // Merkle proof of eligibility, only needed without an earlier bid
+        proof: Option<Vec<Binary>>,
+    },
+    Commit {
+        auction_id: u64,
+        hash: Binary,
+    },
+    Reveal {
+        auction_id: u64,
+        amount: Uint128,
+        salt: Binary,
+    },
+    Close {
+        auction_id: u64,
+    },
+    Cancel {
+        auction_id: u64,
+        reason: String,
+    },
+    TransferOwnership {
+        auction_id: u64,
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {
+        auction_id: u64,
+    },
+    RenounceOwnership {
+        auction_id: u64,
+    },
+    AddOperator {
+        auction_id: u64,
+        operator: String,
+        permissions: Vec<Permission>,
+    },
+    RemoveOperator {
+        auction_id: u64,
+        operator: String,
+    },
+    UpdateMetadata {
+        auction_id: u64,
+        description: String,
+    },
+    UpdateAccessList {
+        auction_id: u64,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    SetMerkleRoot {
+        auction_id: u64,
+        merkle_root: Option<Binary>,
+    },
+    Settle {
+        auction_id: u64,
+    },
+    ClaimFees {
+        auction_id: u64,
+    },
+    Retract {
+        auction_id: u64,
+        recipient: Option<String>,
+    },
+    Receive(Cw20ReceiveMsg),
+    ReceiveNft(Cw721ReceiveMsg),
+}
+
+// Messages embedded in a CW20 or CW721 send to the contract
+#[cw_serde]
+pub enum ReceiveMsg {
+    Bid {
+        auction_id: u64,
+        // Merkle proof of eligibility, only needed on the first bid
+        proof: Option<Vec<Binary>>,
+        // token held in the gating collection, looked up if missing
+        token_id: Option<String>,
+    },
+    BidUnits {
+        auction_id: u64,
+        price: Uint128,
+        quantity: u64,
+    },
+    BuyNow {
+        auction_id: u64,
+        // Merkle proof of eligibility, only needed without an earlier bid
+        proof: Option<Vec<Binary>>,
+    },
+    Commit {
+        auction_id: u64,
+        hash: Binary,
+    },
+    DepositLot {
+        auction_id: u64,
+    },
+    Cancel {
+        auction_id: u64,
+        reason: String,
+    },
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(u128)]
-    TotalBid { address: String },
+    TotalBid { auction_id: u64, address: String },
     #[returns(BidResp)]
-    HighestBid {},
+    HighestBid { auction_id: u64 },
     #[returns(bool)]
-    IsClosed {},
-    #[returns(BidResp)]
-    Winner {},
+    IsClosed { auction_id: u64 },
+    #[returns(WinnerResp)]
+    Winner { auction_id: u64 },
+    #[returns(ScheduleResp)]
+    Schedule { auction_id: u64 },
+    #[returns(Uint128)]
+    MinNextBid { auction_id: u64 },
+    #[returns(AccruedFeesResp)]
+    AccruedFees { auction_id: u64 },
+    #[returns(LotResp)]
+    Lot { auction_id: u64 },
+    #[returns(Uint128)]
+    CurrentPrice { auction_id: u64 },
+    #[returns(Commitment)]
+    Commitment { auction_id: u64, address: String },
+    #[returns(AllocationsResp)]
+    Allocations { auction_id: u64 },
+    #[returns(OwnershipResp)]
+    Ownership { auction_id: u64 },
+    #[returns(OperatorsResp)]
+    Operators { auction_id: u64 },
+    #[returns(GatingResp)]
+    Gating { auction_id: u64 },
+    #[returns(AccessListResp)]
+    AccessList {
+        auction_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(ListAuctionsResp)]
+    ListAuctions {
+        status: Option<Status>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -39,3 +254,93 @@ pub struct BidResp {
     pub address: Addr,
     pub amount: Uint128,
 }
+
+// Outcome of a closed auction
+#[cw_serde]
+pub enum WinnerResp {
+    // the highest bid won the auction, paying the clearing price
+    Won {
+        address: Addr,
+        amount: Uint128,
+        clearing_price: Uint128,
+        // whether the winning bid reached the buy-it-now price
+        bought_now: bool,
+    },
+    // the highest bid is below the reserve price, so nobody won
+    ReserveNotMet {
+        address: Addr,
+        amount: Uint128,
+        reserve_price: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct ScheduleResp {
+    pub start: Option<Scheduled>,
+    // effective end, including soft close extensions
+    pub end: Option<Scheduled>,
+    // total seconds the end has been extended by late bids
+    pub extension: u64,
+}
+
+#[cw_serde]
+pub struct AccruedFeesResp {
+    // commissions accrued by each recipient and not claimed yet
+    pub fees: Vec<(Addr, Uint128)>,
+}
+
+#[cw_serde]
+pub struct LotResp {
+    pub lot: Option<Lot>,
+    // whether the lot is currently held by the contract
+    pub escrowed: bool,
+}
+
+// Units of a multi-unit auction won by each bidder, so far if still open
+#[cw_serde]
+pub struct AllocationsResp {
+    // lowest winning price, paid for each unit
+    pub clearing_price: Option<Uint128>,
+    pub allocations: Vec<(Addr, u64)>,
+}
+
+#[cw_serde]
+pub struct OwnershipResp {
+    // missing once the ownership has been renounced
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct OperatorsResp {
+    pub operators: Vec<(Addr, Vec<Permission>)>,
+}
+
+#[cw_serde]
+pub struct AccessListResp {
+    pub access: AccessControl,
+    pub members: Vec<Addr>,
+}
+
+// Eligibility requirements of the bidders, on top of the access list
+#[cw_serde]
+pub struct GatingResp {
+    pub merkle_root: Option<Binary>,
+    pub group: Option<GroupGate>,
+    pub nft_gate: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct AuctionResp {
+    pub id: u64,
+    pub owner: Addr,
+    pub description: String,
+    // closed as well once the end has passed, even if not settled yet
+    pub status: Status,
+}
+
+#[cw_serde]
+pub struct ListAuctionsResp {
+    pub auctions: Vec<AuctionResp>,
+}
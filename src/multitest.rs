@@ -1,3 +1,4 @@
 pub mod contract;
+pub mod factory;
 #[cfg(test)]
 mod tests;
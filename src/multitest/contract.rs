@@ -1,19 +1,33 @@
-use cosmwasm_std::{Addr, Coin, StdResult, Uint128};
+use cosmwasm_std::{to_binary, Addr, Binary, Coin, StdResult, Uint128};
+use cw20::{Cw20ExecuteMsg, UncheckedDenom};
+use cw721::Cw721ExecuteMsg;
 use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_utils::Expiration;
 
 use crate::{
     contract::{execute, instantiate, query},
-    msg::{BidResp, ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{
+        AccessListResp, AccruedFeesResp, AllocationsResp, BidResp, ExecuteMsg, GatingResp,
+        InstantiateMsg, ListAuctionsResp, LotResp, OperatorsResp, OwnershipResp, QueryMsg,
+        ReceiveMsg, ScheduleResp, WinnerResp,
+    },
+    state::{CommissionPolicy, Commitment, Permission, Status},
     ContractError,
 };
 
-pub struct BidwasmContract(Addr);
+// Contract address along with the auction driven through it
+#[derive(Debug)]
+pub struct BidwasmContract(Addr, u64);
 
 impl BidwasmContract {
     pub fn addr(&self) -> &Addr {
         &self.0
     }
 
+    pub fn id(&self) -> u64 {
+        self.1
+    }
+
     // Store the code and retrieve the store_code_id
     pub fn store_code(app: &mut App) -> u64 {
         let contract = ContractWrapper::new(execute, instantiate, query);
@@ -22,6 +36,7 @@ impl BidwasmContract {
 
     // Perform instantiation for the contract
     #[track_caller]
+    #[allow(clippy::too_many_arguments)]
     pub fn instantiate<'a>(
         app: &mut App,
         code_id: u64,
@@ -31,41 +46,551 @@ impl BidwasmContract {
         denom: &str,
         description: &str,
         commission: impl Into<Option<u128>>,
-    ) -> StdResult<Self> {
+    ) -> Result<Self, ContractError> {
         let owner = owner.into();
-        let commission = commission.into();
+        let commission = commission
+            .into()
+            .map(|commission| CommissionPolicy::Flat(Uint128::new(commission)));
 
-        app.instantiate_contract(
+        Self::instantiate_with_msg(
+            app,
             code_id,
-            sender.clone(),
+            sender,
+            label,
             &InstantiateMsg {
-                denom: denom.to_string(),
+                denom: UncheckedDenom::Native(denom.to_string()),
                 owner: owner.map(Addr::to_string),
                 description: description.to_string(),
                 commission,
+                start: None,
+                end: None,
+                keeper_reward: None,
+                soft_close: None,
+                reserve_price: None,
+                starting_price: None,
+                min_increment: None,
+                commission_recipients: None,
+                proceeds_recipient: None,
+                commission_mode: None,
+                lot: None,
+                kind: None,
+                pricing: None,
+                quantity: None,
+                buy_now_price: None,
+                refund_commissions: None,
+                access: None,
+                merkle_root: None,
+                group: None,
+                nft_gate: None,
             },
-            &[],
-            label,
-            None,
         )
-        .map(BidwasmContract)
-        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Perform instantiation for the contract with a custom message
+    #[track_caller]
+    pub fn instantiate_with_msg(
+        app: &mut App,
+        code_id: u64,
+        sender: &Addr,
+        label: &str,
+        msg: &InstantiateMsg,
+    ) -> Result<Self, ContractError> {
+        Self::instantiate_with_funds(app, code_id, sender, label, msg, &[])
+    }
+
+    // Perform instantiation for the contract with a custom message and funds
+    #[track_caller]
+    pub fn instantiate_with_funds(
+        app: &mut App,
+        code_id: u64,
+        sender: &Addr,
+        label: &str,
+        msg: &InstantiateMsg,
+        funds: &[Coin],
+    ) -> Result<Self, ContractError> {
+        app.instantiate_contract(code_id, sender.clone(), msg, funds, label, None)
+            .map(|addr| BidwasmContract(addr, 0))
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Create another auction in the same contract
+    #[track_caller]
+    pub fn create_auction(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        msg: &InstantiateMsg,
+        funds: &[Coin],
+    ) -> Result<Self, ContractError> {
+        let resp = app
+            .execute_contract(
+                sender.clone(),
+                self.0.clone(),
+                &ExecuteMsg::CreateAuction(Box::new(msg.clone())),
+                funds,
+            )
+            .map_err(|err| err.downcast::<ContractError>().unwrap())?;
+
+        let id = resp
+            .custom_attrs(1)
+            .iter()
+            .find(|attr| attr.key == "auction_id")
+            .unwrap()
+            .value
+            .parse()
+            .unwrap();
+        Ok(BidwasmContract(self.0.clone(), id))
     }
 
     // Perform bidding to the auction
     #[track_caller]
     pub fn bid(&self, app: &mut App, sender: &Addr, funds: &[Coin]) -> Result<(), ContractError> {
-        app.execute_contract(sender.clone(), self.0.clone(), &ExecuteMsg::Bid {}, funds)
-            .map(|_| ())
-            .map_err(|err| err.downcast().unwrap())
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Bid {
+                auction_id: self.1,
+                proof: None,
+                token_id: None,
+            },
+            funds,
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Perform the first bid of an address, proving its eligibility
+    #[track_caller]
+    pub fn bid_with_proof(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        funds: &[Coin],
+        proof: &[Binary],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Bid {
+                auction_id: self.1,
+                proof: Some(proof.to_vec()),
+                token_id: None,
+            },
+            funds,
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Perform bidding to the auction, pointing to a token held in the gating
+    // collection
+    #[track_caller]
+    pub fn bid_with_token(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        funds: &[Coin],
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Bid {
+                auction_id: self.1,
+                proof: None,
+                token_id: Some(token_id.to_string()),
+            },
+            funds,
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Buy the lot at the buy-it-now price
+    #[track_caller]
+    pub fn buy_now(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        funds: &[Coin],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::BuyNow {
+                auction_id: self.1,
+                proof: None,
+            },
+            funds,
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Buy the lot at the buy-it-now price, proving eligibility
+    pub fn buy_now_with_proof(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        funds: &[Coin],
+        proof: &[Binary],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::BuyNow {
+                auction_id: self.1,
+                proof: Some(proof.to_vec()),
+            },
+            funds,
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Bid for some units of a multi-unit auction, sending the deposit
+    #[track_caller]
+    pub fn bid_units(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        price: u128,
+        quantity: u64,
+        funds: &[Coin],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::BidUnits {
+                auction_id: self.1,
+                price: Uint128::new(price),
+                quantity,
+            },
+            funds,
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Commit a sealed bid, sending its deposit
+    #[track_caller]
+    pub fn commit(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hash: &Binary,
+        funds: &[Coin],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Commit {
+                auction_id: self.1,
+                hash: hash.clone(),
+            },
+            funds,
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Reveal a sealed bid
+    #[track_caller]
+    pub fn reveal(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        amount: u128,
+        salt: &[u8],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Reveal {
+                auction_id: self.1,
+                amount: Uint128::new(amount),
+                salt: Binary::from(salt),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Perform bidding to the auction sending CW20 tokens
+    #[track_caller]
+    pub fn bid_cw20(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token: &Addr,
+        amount: u128,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: self.0.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&ReceiveMsg::Bid {
+                    auction_id: self.1,
+                    proof: None,
+                    token_id: None,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Deposit CW20 tokens as the auction lot
+    #[track_caller]
+    pub fn deposit_cw20(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token: &Addr,
+        amount: u128,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: self.0.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&ReceiveMsg::DepositLot { auction_id: self.1 }).unwrap(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Deposit a CW721 token as the auction lot
+    #[track_caller]
+    pub fn deposit_nft(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        collection: &Addr,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            collection.clone(),
+            &Cw721ExecuteMsg::SendNft {
+                contract: self.0.to_string(),
+                token_id: token_id.to_string(),
+                msg: to_binary(&ReceiveMsg::DepositLot { auction_id: self.1 }).unwrap(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
     }
 
     // Closing the auction
     #[track_caller]
     pub fn close(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
-        app.execute_contract(sender.clone(), self.0.clone(), &ExecuteMsg::Close {}, &[])
-            .map(|_| ())
-            .map_err(|err| err.downcast().unwrap())
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Close { auction_id: self.1 },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Settle the auction after its end
+    #[track_caller]
+    pub fn cancel(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        reason: &str,
+        funds: &[Coin],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Cancel {
+                auction_id: self.1,
+                reason: reason.to_string(),
+            },
+            funds,
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    pub fn transfer_ownership(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        new_owner: &Addr,
+        expiry: impl Into<Option<Expiration>>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::TransferOwnership {
+                auction_id: self.1,
+                new_owner: new_owner.to_string(),
+                expiry: expiry.into(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    pub fn accept_ownership(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::AcceptOwnership { auction_id: self.1 },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    pub fn renounce_ownership(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::RenounceOwnership { auction_id: self.1 },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    pub fn add_operator(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        operator: &Addr,
+        permissions: &[Permission],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::AddOperator {
+                auction_id: self.1,
+                operator: operator.to_string(),
+                permissions: permissions.to_vec(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    pub fn remove_operator(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        operator: &Addr,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::RemoveOperator {
+                auction_id: self.1,
+                operator: operator.to_string(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    pub fn update_metadata(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        description: &str,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UpdateMetadata {
+                auction_id: self.1,
+                description: description.to_string(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    pub fn update_access_list(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        add: &[&Addr],
+        remove: &[&Addr],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UpdateAccessList {
+                auction_id: self.1,
+                add: add.iter().map(|address| address.to_string()).collect(),
+                remove: remove.iter().map(|address| address.to_string()).collect(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn set_merkle_root(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        merkle_root: impl Into<Option<Binary>>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::SetMerkleRoot {
+                auction_id: self.1,
+                merkle_root: merkle_root.into(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    pub fn settle(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Settle { auction_id: self.1 },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Claim the accrued commissions
+    #[track_caller]
+    pub fn claim_fees(&self, app: &mut App, sender: &Addr) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::ClaimFees { auction_id: self.1 },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
     }
 
     // Retract the funds
@@ -82,6 +607,7 @@ impl BidwasmContract {
             sender.clone(),
             self.0.clone(),
             &ExecuteMsg::Retract {
+                auction_id: self.1,
                 recipient: recipient.map(Addr::to_string),
             },
             &[],
@@ -94,6 +620,7 @@ impl BidwasmContract {
         app.wrap().query_wasm_smart(
             self.0.clone(),
             &QueryMsg::TotalBid {
+                auction_id: self.1,
                 address: address.to_string(),
             },
         )
@@ -101,17 +628,118 @@ impl BidwasmContract {
 
     pub fn query_highest_bid(&self, app: &App) -> StdResult<BidResp> {
         app.wrap()
-            .query_wasm_smart(self.0.clone(), &QueryMsg::HighestBid {})
+            .query_wasm_smart(self.0.clone(), &QueryMsg::HighestBid { auction_id: self.1 })
     }
 
     pub fn query_is_closed(&self, app: &App) -> StdResult<bool> {
         app.wrap()
-            .query_wasm_smart(self.0.clone(), &QueryMsg::IsClosed {})
+            .query_wasm_smart(self.0.clone(), &QueryMsg::IsClosed { auction_id: self.1 })
+    }
+
+    pub fn query_winner(&self, app: &App) -> StdResult<WinnerResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Winner { auction_id: self.1 })
     }
 
-    pub fn query_winner(&self, app: &App) -> StdResult<BidResp> {
+    pub fn query_min_next_bid(&self, app: &App) -> StdResult<Uint128> {
         app.wrap()
-            .query_wasm_smart(self.0.clone(), &QueryMsg::Winner {})
+            .query_wasm_smart(self.0.clone(), &QueryMsg::MinNextBid { auction_id: self.1 })
+    }
+
+    pub fn query_accrued_fees(&self, app: &App) -> StdResult<AccruedFeesResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::AccruedFees { auction_id: self.1 },
+        )
+    }
+
+    pub fn query_lot(&self, app: &App) -> StdResult<LotResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Lot { auction_id: self.1 })
+    }
+
+    pub fn query_commitment(&self, app: &App, address: &Addr) -> StdResult<Commitment> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Commitment {
+                auction_id: self.1,
+                address: address.to_string(),
+            },
+        )
+    }
+
+    pub fn query_current_price(&self, app: &App) -> StdResult<Uint128> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::CurrentPrice { auction_id: self.1 },
+        )
+    }
+
+    pub fn query_allocations(&self, app: &App) -> StdResult<AllocationsResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Allocations { auction_id: self.1 },
+        )
+    }
+
+    pub fn query_ownership(&self, app: &App) -> StdResult<OwnershipResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Ownership { auction_id: self.1 })
+    }
+
+    pub fn query_gating(&self, app: &App) -> StdResult<GatingResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Gating { auction_id: self.1 })
+    }
+
+    pub fn query_operators(&self, app: &App) -> StdResult<OperatorsResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Operators { auction_id: self.1 })
+    }
+
+    pub fn query_access_list<'a>(
+        &self,
+        app: &App,
+        start_after: impl Into<Option<&'a Addr>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<AccessListResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::AccessList {
+                auction_id: self.1,
+                start_after: start_after.into().map(Addr::to_string),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    pub fn query_list_auctions(
+        &self,
+        app: &App,
+        status: impl Into<Option<Status>>,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<ListAuctionsResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::ListAuctions {
+                status: status.into(),
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )
+    }
+
+    pub fn query_schedule(&self, app: &App) -> StdResult<ScheduleResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Schedule { auction_id: self.1 })
+    }
+}
+
+// Drive the first auction of the contract at the given address
+impl From<Addr> for BidwasmContract {
+    fn from(addr: Addr) -> Self {
+        BidwasmContract(addr, 0)
     }
 }
 
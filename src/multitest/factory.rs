@@ -0,0 +1,114 @@
+use cosmwasm_std::{Addr, Coin, StdResult, Timestamp};
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+use crate::{
+    factory::{
+        contract::{execute, instantiate, query, reply},
+        msg::{AuctionsResp, ConfigResp, ExecuteMsg, InstantiateMsg, QueryMsg},
+    },
+    msg::InstantiateMsg as AuctionInstantiateMsg,
+    state::Status,
+    ContractError,
+};
+
+#[derive(Debug)]
+pub struct FactoryContract(Addr);
+
+impl FactoryContract {
+    pub fn addr(&self) -> &Addr {
+        &self.0
+    }
+
+    // Store the code and retrieve the store_code_id
+    pub fn store_code(app: &mut App) -> u64 {
+        let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+        app.store_code(Box::new(contract))
+    }
+
+    // Perform instantiation for the factory
+    #[track_caller]
+    pub fn instantiate(
+        app: &mut App,
+        code_id: u64,
+        sender: &Addr,
+        label: &str,
+        auction_code_id: u64,
+    ) -> Result<Self, ContractError> {
+        app.instantiate_contract(
+            code_id,
+            sender.clone(),
+            &InstantiateMsg {
+                code_id: auction_code_id,
+            },
+            &[],
+            label,
+            None,
+        )
+        .map(FactoryContract)
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    // Create an auction through the factory, returning its address
+    #[track_caller]
+    pub fn create_auction(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        msg: &AuctionInstantiateMsg,
+        funds: &[Coin],
+    ) -> Result<Addr, ContractError> {
+        let resp = app
+            .execute_contract(
+                sender.clone(),
+                self.0.clone(),
+                &ExecuteMsg::CreateAuction(Box::new(msg.clone())),
+                funds,
+            )
+            .map_err(|err| err.downcast::<ContractError>().unwrap())?;
+
+        let address = resp
+            .events
+            .iter()
+            .flat_map(|ev| &ev.attributes)
+            .find(|attr| attr.key == "auction_address")
+            .unwrap()
+            .value
+            .clone();
+        Ok(Addr::unchecked(address))
+    }
+
+    pub fn query_config(&self, app: &App) -> StdResult<ConfigResp> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Config {})
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_auctions<'a>(
+        &self,
+        app: &App,
+        owner: impl Into<Option<&'a Addr>>,
+        status: impl Into<Option<Status>>,
+        created_after: impl Into<Option<Timestamp>>,
+        created_before: impl Into<Option<Timestamp>>,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<AuctionsResp> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Auctions {
+                owner: owner.into().map(Addr::to_string),
+                status: status.into(),
+                created_after: created_after.into(),
+                created_before: created_before.into(),
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )
+    }
+}
+
+impl From<FactoryContract> for Addr {
+    fn from(contract: FactoryContract) -> Self {
+        contract.0
+    }
+}
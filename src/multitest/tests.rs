@@ -1,16 +1,66 @@
-use cosmwasm_std::{coins, Addr, Uint128};
-use cw_multi_test::App;
+use cosmwasm_std::{coins, Addr, Binary, Decimal, Empty, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, Cw20QueryMsg, Denom, UncheckedDenom};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw4::Member;
+use cw4_group::msg::{ExecuteMsg as Cw4GroupExecuteMsg, InstantiateMsg as Cw4GroupInstantiateMsg};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw721_base::{
+    ExecuteMsg as Cw721BaseExecuteMsg, Extension, InstantiateMsg as Cw721InstantiateMsg, MintMsg,
+};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_utils::{Expiration, Scheduled};
+use sha2::{Digest, Sha256};
 
 use crate::{
-    msg::{BidResp, QueryMsg},
-    state::{Config, State, Status, BIDS, CONFIG, STATE},
+    factory::msg::{AuctionRecordResp, AuctionsResp, ConfigResp},
+    msg::{
+        AccessListResp, AccruedFeesResp, AllocationsResp, AuctionResp, BidResp, ExecuteMsg,
+        GatingResp, InstantiateMsg, ListAuctionsResp, LotResp, OperatorsResp, OwnershipResp,
+        QueryMsg, ScheduleResp, UncheckedGroupGate, UncheckedLot, WinnerResp,
+    },
+    state::{
+        commitment_hash, AccessControl, AuctionKind, CommissionMode, CommissionPolicy,
+        CommissionTier, Commitment, Config, Decline, DutchAuction, Increment, Lot, Permission,
+        Pricing, SealedAuction, SoftClose, State, Status, UnrevealedDeposits, BIDS, CONFIGS,
+        STATES,
+    },
     ContractError,
 };
 
-use super::contract::BidwasmContract;
+use super::{contract::BidwasmContract, factory::FactoryContract};
 
 const UATOM: &str = "uatom";
 
+// Instantiate message with every optional feature disabled
+fn base_msg(owner: &Addr) -> InstantiateMsg {
+    InstantiateMsg {
+        denom: UncheckedDenom::Native(UATOM.to_string()),
+        owner: Some(owner.to_string()),
+        description: "Supercomputer #2207 bidding".to_string(),
+        commission: None,
+        start: None,
+        end: None,
+        keeper_reward: None,
+        soft_close: None,
+        reserve_price: None,
+        starting_price: None,
+        min_increment: None,
+        commission_recipients: None,
+        proceeds_recipient: None,
+        commission_mode: None,
+        lot: None,
+        kind: None,
+        pricing: None,
+        quantity: None,
+        buy_now_price: None,
+        refund_commissions: None,
+        access: None,
+        merkle_root: None,
+        group: None,
+        nft_gate: None,
+    }
+}
+
 // START --> Auction Opening Tests
 
 #[test]
@@ -37,7 +87,10 @@ fn open_auction_with_owner() {
     .unwrap();
 
     // Query the contract state
-    let state = STATE.query(&app.wrap(), contract.addr().clone()).unwrap();
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
 
     // Verify that contract state is correct
     assert_eq!(
@@ -45,20 +98,52 @@ fn open_auction_with_owner() {
         State {
             current_status: Status::Open,
             highest_bid: None,
+            runner_up: None,
+            commission_pool: Uint128::zero(),
+            end: None,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
         }
     );
 
     // Query the contract configuration
-    let config = CONFIG.query(&app.wrap(), contract.addr().clone()).unwrap();
+    let config = CONFIGS
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
 
     // Verify that contract configuration is correct
     assert_eq!(
         config,
         Config {
-            denom: UATOM.to_string(),
+            denom: Denom::Native(UATOM.to_string()),
             owner,
             description: "Supercomputer #2207 bidding".to_string(),
-            commission: 500_000
+            commission: CommissionPolicy::Flat(Uint128::new(500_000)),
+            start: None,
+            end: None,
+            keeper_reward: Uint128::zero(),
+            soft_close: None,
+            reserve_price: None,
+            starting_price: None,
+            min_increment: None,
+            commission_recipients: vec![],
+            proceeds_recipient: None,
+            commission_mode: CommissionMode::Push,
+            lot: None,
+            kind: AuctionKind::English,
+            pricing: Pricing::FirstPrice,
+            quantity: None,
+            buy_now_price: None,
+            refund_commissions: false,
+            renounced: false,
+            access: AccessControl::Open,
+            merkle_root: None,
+            group: None,
+            nft_gate: None,
+            created_at: app.block_info().time,
         }
     );
 }
@@ -86,7 +171,10 @@ fn open_auction_without_owner() {
     .unwrap();
 
     // Query the contract state
-    let state = STATE.query(&app.wrap(), contract.addr().clone()).unwrap();
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
 
     // Verify that contract state is correct
     assert_eq!(
@@ -94,21 +182,53 @@ fn open_auction_without_owner() {
         State {
             current_status: Status::Open,
             highest_bid: None,
+            runner_up: None,
+            commission_pool: Uint128::zero(),
+            end: None,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
         }
     );
 
     // Query the contract configuration
-    let config = CONFIG.query(&app.wrap(), contract.addr().clone()).unwrap();
+    let config = CONFIGS
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
 
     // Verify that contract configuration is correct (if no owner is provided,
     // default owner is the contract creator).
     assert_eq!(
         config,
         Config {
-            denom: UATOM.to_string(),
+            denom: Denom::Native(UATOM.to_string()),
             owner,
             description: "Supercomputer #2207 bidding".to_string(),
-            commission: 500_000
+            commission: CommissionPolicy::Flat(Uint128::new(500_000)),
+            start: None,
+            end: None,
+            keeper_reward: Uint128::zero(),
+            soft_close: None,
+            reserve_price: None,
+            starting_price: None,
+            min_increment: None,
+            commission_recipients: vec![],
+            proceeds_recipient: None,
+            commission_mode: CommissionMode::Push,
+            lot: None,
+            kind: AuctionKind::English,
+            pricing: Pricing::FirstPrice,
+            quantity: None,
+            buy_now_price: None,
+            refund_commissions: false,
+            renounced: false,
+            access: AccessControl::Open,
+            merkle_root: None,
+            group: None,
+            nft_gate: None,
+            created_at: app.block_info().time,
         }
     );
 }
@@ -136,7 +256,10 @@ fn open_auction_without_commission() {
     .unwrap();
 
     // Query the contract state
-    let state = STATE.query(&app.wrap(), contract.addr().clone()).unwrap();
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
 
     // Verify that contract state is correct
     assert_eq!(
@@ -144,21 +267,53 @@ fn open_auction_without_commission() {
         State {
             current_status: Status::Open,
             highest_bid: None,
+            runner_up: None,
+            commission_pool: Uint128::zero(),
+            end: None,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
         }
     );
 
     // Query the contract configuration
-    let config = CONFIG.query(&app.wrap(), contract.addr().clone()).unwrap();
+    let config = CONFIGS
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
 
     // Verify that contract configuration is correct (if no owner is provided,
     // default owner is the contract creator).
     assert_eq!(
         config,
         Config {
-            denom: UATOM.to_string(),
+            denom: Denom::Native(UATOM.to_string()),
             owner,
             description: "Supercomputer #2207 bidding".to_string(),
-            commission: 0
+            commission: CommissionPolicy::Flat(Uint128::zero()),
+            start: None,
+            end: None,
+            keeper_reward: Uint128::zero(),
+            soft_close: None,
+            reserve_price: None,
+            starting_price: None,
+            min_increment: None,
+            commission_recipients: vec![],
+            proceeds_recipient: None,
+            commission_mode: CommissionMode::Push,
+            lot: None,
+            kind: AuctionKind::English,
+            pricing: Pricing::FirstPrice,
+            quantity: None,
+            buy_now_price: None,
+            refund_commissions: false,
+            renounced: false,
+            access: AccessControl::Open,
+            merkle_root: None,
+            group: None,
+            nft_gate: None,
+            created_at: app.block_info().time,
         }
     );
 }
@@ -305,7 +460,11 @@ fn simple_bid_no_commission() {
         .unwrap();
 
     let bid = BIDS
-        .query(&app.wrap(), contract.addr().clone(), &sender)
+        .query(
+            &app.wrap(),
+            contract.addr().clone(),
+            (contract.id(), &sender),
+        )
         .unwrap();
 
     // Check if bid is stored in the state
@@ -358,7 +517,11 @@ fn simple_bid() {
         .unwrap();
 
     let bid = BIDS
-        .query(&app.wrap(), contract.addr().clone(), &sender)
+        .query(
+            &app.wrap(),
+            contract.addr().clone(),
+            (contract.id(), &sender),
+        )
         .unwrap();
 
     // Check if bid is stored in the state
@@ -380,225 +543,294 @@ fn simple_bid() {
     );
 }
 
-// END --> Bidding Tests
-
-// START --> Close Tests
-
 #[test]
-fn close_auction() {
-    // Define participant
+fn starting_price_and_absolute_increment() {
+    // Define participants
     let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
 
-    let mut app = App::default();
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(10_000_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(10_000_000, UATOM))
+            .unwrap();
+    });
 
     let code_id = BidwasmContract::store_code(&mut app);
 
-    // Instantiate contract
-    let contract = BidwasmContract::instantiate(
+    // Bids start from 2_000_000 and must raise by at least 500_000
+    let contract = BidwasmContract::instantiate_with_msg(
         &mut app,
         code_id,
         &owner,
         "Bidwasm contract",
-        None,
-        UATOM,
-        "Supercomputer #2207 bidding",
-        500_000,
+        &InstantiateMsg {
+            starting_price: Some(Uint128::new(2_000_000)),
+            min_increment: Some(Increment::Absolute(Uint128::new(500_000))),
+            ..base_msg(&owner)
+        },
     )
     .unwrap();
 
-    // Check the status is open
-    let state = STATE.query(&app.wrap(), contract.addr().clone()).unwrap();
     assert_eq!(
-        state,
-        State {
-            current_status: Status::Open,
-            highest_bid: None
+        contract.query_min_next_bid(&app).unwrap(),
+        Uint128::new(2_000_000)
+    );
+
+    // The first bid must reach the starting price
+    let err = contract
+        .bid(&mut app, &sender1, &coins(1_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientIncrement {
+            new_bid: Uint128::new(1_000_000),
+            min_bid: Uint128::new(2_000_000)
         }
     );
 
-    // Close the auction
-    contract.close(&mut app, &owner).unwrap();
+    contract
+        .bid(&mut app, &sender1, &coins(2_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_min_next_bid(&app).unwrap(),
+        Uint128::new(2_500_000)
+    );
 
-    // Check the status is closed
-    let state = STATE.query(&app.wrap(), contract.addr().clone()).unwrap();
+    // Outbidding by less than the increment fails
+    let err = contract
+        .bid(&mut app, &sender2, &coins(2_400_000, UATOM))
+        .unwrap_err();
     assert_eq!(
-        state,
-        State {
-            current_status: Status::Closed,
-            highest_bid: None
+        err,
+        ContractError::InsufficientIncrement {
+            new_bid: Uint128::new(2_400_000),
+            min_bid: Uint128::new(2_500_000)
         }
     );
+
+    contract
+        .bid(&mut app, &sender2, &coins(2_500_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_min_next_bid(&app).unwrap(),
+        Uint128::new(3_000_000)
+    );
 }
 
 #[test]
-fn close_auction_after_bid() {
-    // Define participant
+fn percentage_increment() {
+    // Define participants
     let owner = Addr::unchecked("owner");
-    let sender = Addr::unchecked("sender");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
 
     let mut app = App::new(|router, _api, storage| {
         router
             .bank
-            .init_balance(storage, &sender, coins(4_000_000, UATOM))
+            .init_balance(storage, &sender1, coins(10_000_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(10_000_000, UATOM))
             .unwrap();
     });
 
     let code_id = BidwasmContract::store_code(&mut app);
 
-    // Instantiate contract
-    let contract = BidwasmContract::instantiate(
+    // Bids must raise the highest one by at least 10%
+    let contract = BidwasmContract::instantiate_with_msg(
         &mut app,
         code_id,
         &owner,
         "Bidwasm contract",
-        None,
-        UATOM,
-        "Supercomputer #2207 bidding",
-        500_000,
+        &InstantiateMsg {
+            min_increment: Some(Increment::Percentage(Decimal::percent(10))),
+            ..base_msg(&owner)
+        },
     )
     .unwrap();
 
-    // Making a simple bid
-    contract
-        .bid(&mut app, &sender, &coins(2_000_000, UATOM))
-        .unwrap();
+    // Without any bid, any positive amount takes the lead
+    assert_eq!(contract.query_min_next_bid(&app).unwrap(), Uint128::one());
 
-    let bid = BIDS
-        .query(&app.wrap(), contract.addr().clone(), &sender)
+    contract
+        .bid(&mut app, &sender1, &coins(4_000_000, UATOM))
         .unwrap();
-
-    // Check if bid is stored in the state
-    assert_eq!(bid, Some(Uint128::new(1_500_000)));
-
-    // sender should have not any balance
-    assert_eq!(
-        app.wrap().query_all_balances(&sender).unwrap(),
-        coins(2_000_000, UATOM)
-    );
-
-    // owner should have got the commission
     assert_eq!(
-        app.wrap().query_all_balances(&owner).unwrap(),
-        coins(500_000, UATOM)
-    );
-
-    // contract should store bid minus commission
-    assert_eq!(
-        app.wrap().query_all_balances(contract.addr()).unwrap(),
-        coins(1_500_000, UATOM)
+        contract.query_min_next_bid(&app).unwrap(),
+        Uint128::new(4_400_000)
     );
 
-    // Check the status is open
-    let state = STATE.query(&app.wrap(), contract.addr().clone()).unwrap();
+    let err = contract
+        .bid(&mut app, &sender2, &coins(4_300_000, UATOM))
+        .unwrap_err();
     assert_eq!(
-        state,
-        State {
-            current_status: Status::Open,
-            highest_bid: Some((sender.clone(), Uint128::new(1_500_000)))
+        err,
+        ContractError::InsufficientIncrement {
+            new_bid: Uint128::new(4_300_000),
+            min_bid: Uint128::new(4_400_000)
         }
     );
 
-    // Close the auction
-    contract.close(&mut app, &owner).unwrap();
-
-    // Check the status is closed
-    let state = STATE.query(&app.wrap(), contract.addr().clone()).unwrap();
-    assert_eq!(
-        state,
-        State {
-            current_status: Status::Closed,
-            highest_bid: Some((sender.clone(), Uint128::new(1_500_000)))
-        }
-    );
+    contract
+        .bid(&mut app, &sender2, &coins(4_400_000, UATOM))
+        .unwrap();
+}
 
-    // sender should have the balance minus bid and commission
-    assert_eq!(
-        app.wrap().query_all_balances(&sender).unwrap(),
-        coins(2_000_000, UATOM)
-    );
+#[test]
+fn invalid_commission() {
+    // Define participant
+    let owner = Addr::unchecked("owner");
 
-    // owner should have got the commission and the bid
-    assert_eq!(
-        app.wrap().query_all_balances(&owner).unwrap(),
-        coins(2_000_000, UATOM)
-    );
+    let mut app = App::default();
 
-    // contract should store nothing
-    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
+    let code_id = BidwasmContract::store_code(&mut app);
 
-    // Try making another bid after closing the auction
-    let err = contract
-        .bid(&mut app, &sender, &coins(2_000_000, UATOM))
+    // Percentages cannot exceed 100%, bounds must be ordered and tiers sorted
+    for commission in [
+        CommissionPolicy::Percentage { bps: 10_001 },
+        CommissionPolicy::ClampedPercentage {
+            bps: 100,
+            min: Uint128::new(2_000),
+            max: Uint128::new(1_000),
+        },
+        CommissionPolicy::Tiered(vec![]),
+        CommissionPolicy::Tiered(vec![
+            CommissionTier {
+                from: Uint128::new(1_000),
+                bps: 100,
+            },
+            CommissionTier {
+                from: Uint128::zero(),
+                bps: 200,
+            },
+        ]),
+    ] {
+        let err = BidwasmContract::instantiate_with_msg(
+            &mut app,
+            code_id,
+            &owner,
+            "Bidwasm contract",
+            &InstantiateMsg {
+                commission: Some(commission),
+                ..base_msg(&owner)
+            },
+        )
         .unwrap_err();
-
-    // Verify that the contract fails if the sender bids after the acution is
-    // closed
-    assert_eq!(err, ContractError::ClosedAcution);
+        assert_eq!(err, ContractError::InvalidCommission);
+    }
 }
 
 #[test]
-fn invalid_close_unauthorized() {
-    // Define participant
+fn percentage_commission() {
+    // Define participants
     let owner = Addr::unchecked("owner");
     let sender = Addr::unchecked("sender");
 
-    let mut app = App::default();
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(4_000_000, UATOM))
+            .unwrap();
+    });
 
     let code_id = BidwasmContract::store_code(&mut app);
 
-    // Instantiate contract
-    let contract = BidwasmContract::instantiate(
+    // Commission is 2.5% of the funds sent
+    let contract = BidwasmContract::instantiate_with_msg(
         &mut app,
         code_id,
         &owner,
         "Bidwasm contract",
-        None,
-        UATOM,
-        "Supercomputer #2207 bidding",
-        500_000,
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Percentage { bps: 250 }),
+            ..base_msg(&owner)
+        },
     )
     .unwrap();
 
-    // Check the status is open
-    let state = STATE.query(&app.wrap(), contract.addr().clone()).unwrap();
+    contract
+        .bid(&mut app, &sender, &coins(4_000_000, UATOM))
+        .unwrap();
+
     assert_eq!(
-        state,
-        State {
-            current_status: Status::Open,
-            highest_bid: None
-        }
+        contract.query_total_bid(&app, &sender).unwrap(),
+        Uint128::new(3_900_000)
     );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(100_000, UATOM)
+    );
+}
 
-    // Try closing the auction from unauthorized account
-    let err = contract.close(&mut app, &sender).unwrap_err();
+#[test]
+fn clamped_percentage_commission() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
 
-    // Verify that the contract fails if the auction is closed by unauthorized
-    // address
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(100_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(20_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Commission is 1% of the funds, with a minimum fee of 150_000
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::ClampedPercentage {
+                bps: 100,
+                min: Uint128::new(150_000),
+                max: Uint128::new(100_000_000),
+            }),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // The minimum fee is higher than the funds sent
+    let err = contract
+        .bid(&mut app, &sender1, &coins(100_000, UATOM))
+        .unwrap_err();
     assert_eq!(
         err,
-        ContractError::Unauthorized {
-            owner: owner.to_string()
+        ContractError::InsufficientFundsForCommission {
+            funds: Uint128::new(100_000),
+            commission: Uint128::new(150_000)
         }
     );
 
-    // Check the status is open
-    let state = STATE.query(&app.wrap(), contract.addr().clone()).unwrap();
+    // 1% of 20_000_000 is above the minimum fee
+    contract
+        .bid(&mut app, &sender2, &coins(20_000_000, UATOM))
+        .unwrap();
     assert_eq!(
-        state,
-        State {
-            current_status: Status::Open,
-            highest_bid: None
-        }
+        contract.query_total_bid(&app, &sender2).unwrap(),
+        Uint128::new(19_800_000)
     );
 }
 
-// END --> Close Tests
-
-// START --> Retract Tests
-
 #[test]
-fn retract() {
-    // Define participant
+fn tiered_commission() {
+    // Define participants
     let owner = Addr::unchecked("owner");
     let sender1 = Addr::unchecked("sender1");
     let sender2 = Addr::unchecked("sender2");
@@ -606,181 +838,177 @@ fn retract() {
     let mut app = App::new(|router, _api, storage| {
         router
             .bank
-            .init_balance(storage, &sender1, coins(4_500_000, UATOM))
+            .init_balance(storage, &sender1, coins(1_000_000, UATOM))
             .unwrap();
         router
             .bank
-            .init_balance(storage, &sender2, coins(7_500_000, UATOM))
+            .init_balance(storage, &sender2, coins(20_000_000, UATOM))
             .unwrap();
     });
 
     let code_id = BidwasmContract::store_code(&mut app);
 
-    // Instantiate contract
-    let contract = BidwasmContract::instantiate(
+    // Commission is 5% below 10_000_000 and 1% from 10_000_000
+    let contract = BidwasmContract::instantiate_with_msg(
         &mut app,
         code_id,
         &owner,
         "Bidwasm contract",
-        None,
-        UATOM,
-        "Supercomputer #2207 bidding",
-        500_000,
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Tiered(vec![
+                CommissionTier {
+                    from: Uint128::zero(),
+                    bps: 500,
+                },
+                CommissionTier {
+                    from: Uint128::new(10_000_000),
+                    bps: 100,
+                },
+            ])),
+            ..base_msg(&owner)
+        },
     )
     .unwrap();
 
-    // Sender1 make a bid of 4_000_000
     contract
-        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
+        .bid(&mut app, &sender1, &coins(1_000_000, UATOM))
         .unwrap();
-
-    // Sender2 make a bid of 7_000_000
     contract
-        .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
+        .bid(&mut app, &sender2, &coins(20_000_000, UATOM))
         .unwrap();
 
-    // Check that the bids are registered correctly
-    let sender1_bid = BIDS
-        .query(&app.wrap(), contract.addr().clone(), &sender1)
-        .unwrap();
+    assert_eq!(
+        contract.query_total_bid(&app, &sender1).unwrap(),
+        Uint128::new(950_000)
+    );
+    assert_eq!(
+        contract.query_total_bid(&app, &sender2).unwrap(),
+        Uint128::new(19_800_000)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(250_000, UATOM)
+    );
+}
 
-    assert_eq!(sender1_bid, Some(Uint128::new(4_000_000)));
+#[test]
+fn invalid_commission_split() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let treasury = Addr::unchecked("treasury");
 
-    let sender2_bid = BIDS
-        .query(&app.wrap(), contract.addr().clone(), &sender2)
-        .unwrap();
-
-    assert_eq!(sender2_bid, Some(Uint128::new(7_000_000)));
-
-    // senders should have not any balance
-    assert_eq!(app.wrap().query_all_balances(&sender1).unwrap(), &[]);
-    assert_eq!(app.wrap().query_all_balances(&sender2).unwrap(), &[]);
-
-    // owner should have got the commission
-    assert_eq!(
-        app.wrap().query_all_balances(&owner).unwrap(),
-        coins(1_000_000, UATOM)
-    );
-
-    // contract should store bid minus commission
-    assert_eq!(
-        app.wrap().query_all_balances(contract.addr()).unwrap(),
-        coins(11_000_000, UATOM)
-    );
-
-    // Close the auction
-    contract.close(&mut app, &owner).unwrap();
-
-    // Check the status is closed
-    let state = STATE.query(&app.wrap(), contract.addr().clone()).unwrap();
-    assert_eq!(
-        state,
-        State {
-            current_status: Status::Closed,
-            highest_bid: Some((sender2.clone(), Uint128::new(7_000_000)))
-        }
-    );
-
-    // Sender1 retracting funds since sender did not win the auction
-    contract.retract(&mut app, &sender1, None).unwrap();
-
-    // Sender1 should have the original balance minus the commission for the
-    // bid
-    assert_eq!(
-        app.wrap().query_all_balances(&sender1).unwrap(),
-        coins(4_000_000, UATOM)
-    );
-
-    // Sender2 should have not any balance
-    assert_eq!(app.wrap().query_all_balances(&sender2).unwrap(), &[]);
+    let mut app = App::default();
 
-    // owner should have got the commission plus the highest bid at the time
-    // of the auction closure
-    assert_eq!(
-        app.wrap().query_all_balances(&owner).unwrap(),
-        coins(8_000_000, UATOM)
-    );
+    let code_id = BidwasmContract::store_code(&mut app);
 
-    // contract should have not any balance
-    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
+    // Shares must sum to 1 and recipients must be distinct
+    for recipients in [
+        vec![(treasury.to_string(), Decimal::percent(60))],
+        vec![
+            (treasury.to_string(), Decimal::percent(50)),
+            (treasury.to_string(), Decimal::percent(50)),
+        ],
+        vec![
+            (treasury.to_string(), Decimal::one()),
+            (owner.to_string(), Decimal::zero()),
+        ],
+    ] {
+        let err = BidwasmContract::instantiate_with_msg(
+            &mut app,
+            code_id,
+            &owner,
+            "Bidwasm contract",
+            &InstantiateMsg {
+                commission_recipients: Some(recipients),
+                ..base_msg(&owner)
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidCommissionSplit);
+    }
 }
 
 #[test]
-fn retract_on_another_recipient() {
-    // Define participant
+fn commission_split_and_proceeds_recipient() {
+    // Define participants
     let owner = Addr::unchecked("owner");
-    let sender1 = Addr::unchecked("sender1");
-    let sender2 = Addr::unchecked("sender2");
-    let recipient = Addr::unchecked("recipient");
+    let treasury = Addr::unchecked("treasury");
+    let seller = Addr::unchecked("seller");
+    let curator = Addr::unchecked("curator");
+    let sender = Addr::unchecked("sender");
 
     let mut app = App::new(|router, _api, storage| {
         router
             .bank
-            .init_balance(storage, &sender1, coins(4_500_000, UATOM))
-            .unwrap();
-        router
-            .bank
-            .init_balance(storage, &sender2, coins(7_500_000, UATOM))
+            .init_balance(storage, &sender, coins(4_000_001, UATOM))
             .unwrap();
     });
 
     let code_id = BidwasmContract::store_code(&mut app);
 
-    // Instantiate contract
-    let contract = BidwasmContract::instantiate(
+    // Commissions are split 50/30/20 and proceeds go to the seller
+    let contract = BidwasmContract::instantiate_with_msg(
         &mut app,
         code_id,
         &owner,
         "Bidwasm contract",
-        None,
-        UATOM,
-        "Supercomputer #2207 bidding",
-        500_000,
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Flat(Uint128::new(1_000_001))),
+            commission_recipients: Some(vec![
+                (treasury.to_string(), Decimal::percent(50)),
+                (seller.to_string(), Decimal::percent(30)),
+                (curator.to_string(), Decimal::percent(20)),
+            ]),
+            proceeds_recipient: Some(seller.to_string()),
+            ..base_msg(&owner)
+        },
     )
     .unwrap();
 
-    // Sender1 make a bid of 4_000_000
-    contract
-        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
-        .unwrap();
-
-    // Sender2 make a bid of 7_000_000
-    contract
-        .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
+    // The split is reported in the response, rounding dust goes to the first
+    // recipient
+    let resp = app
+        .execute_contract(
+            sender.clone(),
+            contract.addr().clone(),
+            &ExecuteMsg::Bid {
+                auction_id: contract.id(),
+                proof: None,
+                token_id: None,
+            },
+            &coins(4_000_001, UATOM),
+        )
         .unwrap();
+    let wasm = resp.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+    assert!(wasm
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "commission_split"
+            && attr.value == "treasury:500001,seller:300000,curator:200000"));
 
-    // Close the auction
-    contract.close(&mut app, &owner).unwrap();
-
-    // Sender1 retracting funds since sender did not win the auction
-    contract.retract(&mut app, &sender1, &recipient).unwrap();
-
-    // Recipient should have the original balance minus the commission for the
-    // bid
     assert_eq!(
-        app.wrap().query_all_balances(&recipient).unwrap(),
-        coins(4_000_000, UATOM)
+        app.wrap().query_all_balances(&treasury).unwrap(),
+        coins(500_001, UATOM)
     );
-
-    // Sender1 and Sender2 should have not any balance
-    assert_eq!(app.wrap().query_all_balances(&sender1).unwrap(), &[]);
-    assert_eq!(app.wrap().query_all_balances(&sender2).unwrap(), &[]);
-
-    // owner should have got the commission plus the highest bid at the time
-    // of the auction closure
     assert_eq!(
-        app.wrap().query_all_balances(&owner).unwrap(),
-        coins(8_000_000, UATOM)
+        app.wrap().query_all_balances(&curator).unwrap(),
+        coins(200_000, UATOM)
     );
 
-    // contract should have not any balance
-    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
+    // The winning bid goes to the seller, not to the owner
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&seller).unwrap(),
+        coins(3_300_000, UATOM)
+    );
+    assert_eq!(app.wrap().query_all_balances(&owner).unwrap(), &[]);
 }
 
 #[test]
-fn invalid_retract_by_winner() {
-    // Define participant
+fn accrued_commissions() {
+    // Define participants
     let owner = Addr::unchecked("owner");
+    let treasury = Addr::unchecked("treasury");
     let sender1 = Addr::unchecked("sender1");
     let sender2 = Addr::unchecked("sender2");
 
@@ -797,56 +1025,80 @@ fn invalid_retract_by_winner() {
 
     let code_id = BidwasmContract::store_code(&mut app);
 
-    // Instantiate contract
-    let contract = BidwasmContract::instantiate(
+    // Commissions are accrued and split between owner and treasury
+    let contract = BidwasmContract::instantiate_with_msg(
         &mut app,
         code_id,
         &owner,
         "Bidwasm contract",
-        None,
-        UATOM,
-        "Supercomputer #2207 bidding",
-        500_000,
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Flat(Uint128::new(500_000))),
+            commission_recipients: Some(vec![
+                (owner.to_string(), Decimal::percent(80)),
+                (treasury.to_string(), Decimal::percent(20)),
+            ]),
+            commission_mode: Some(CommissionMode::Accrue),
+            ..base_msg(&owner)
+        },
     )
     .unwrap();
 
-    // Sender1 make a bid of 4_000_000
     contract
         .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
         .unwrap();
-
-    // Sender2 make a bid of 7_000_000
     contract
         .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
         .unwrap();
 
-    // Close the auction
-    contract.close(&mut app, &owner).unwrap();
+    // Nothing is sent, the contract keeps the commissions
+    assert_eq!(app.wrap().query_all_balances(&owner).unwrap(), &[]);
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        coins(12_000_000, UATOM)
+    );
+    assert_eq!(
+        contract.query_accrued_fees(&app).unwrap(),
+        AccruedFeesResp {
+            fees: vec![
+                (owner.clone(), Uint128::new(800_000)),
+                (treasury.clone(), Uint128::new(200_000)),
+            ]
+        }
+    );
 
-    // Sender2 tries retracting funds even if he won the auction
-    let err = contract.retract(&mut app, &sender2, None).unwrap_err();
+    // Each recipient claims its own share
+    contract.claim_fees(&mut app, &owner).unwrap();
+    contract.claim_fees(&mut app, &treasury).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(800_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&treasury).unwrap(),
+        coins(200_000, UATOM)
+    );
+    assert_eq!(
+        contract.query_accrued_fees(&app).unwrap(),
+        AccruedFeesResp { fees: vec![] }
+    );
 
-    // Verify that the contract fails
-    assert_eq!(err, ContractError::InvalidRetract);
+    // Nothing left to claim
+    let err = contract.claim_fees(&mut app, &treasury).unwrap_err();
+    assert_eq!(err, ContractError::NoAccruedFees);
+    let err = contract.claim_fees(&mut app, &sender1).unwrap_err();
+    assert_eq!(err, ContractError::NoAccruedFees);
 }
 
+// END --> Bidding Tests
+
+// START --> Close Tests
+
 #[test]
-fn invalid_double_retract() {
+fn close_auction() {
     // Define participant
     let owner = Addr::unchecked("owner");
-    let sender1 = Addr::unchecked("sender1");
-    let sender2 = Addr::unchecked("sender2");
 
-    let mut app = App::new(|router, _api, storage| {
-        router
-            .bank
-            .init_balance(storage, &sender1, coins(4_500_000, UATOM))
-            .unwrap();
-        router
-            .bank
-            .init_balance(storage, &sender2, coins(7_500_000, UATOM))
-            .unwrap();
-    });
+    let mut app = App::default();
 
     let code_id = BidwasmContract::store_code(&mut app);
 
@@ -863,44 +1115,60 @@ fn invalid_double_retract() {
     )
     .unwrap();
 
-    // Sender1 make a bid of 4_000_000
-    contract
-        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
-        .unwrap();
-
-    // Sender2 make a bid of 7_000_000
-    contract
-        .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
+    // Check the status is open
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
         .unwrap();
+    assert_eq!(
+        state,
+        State {
+            current_status: Status::Open,
+            highest_bid: None,
+            runner_up: None,
+            commission_pool: Uint128::zero(),
+            end: None,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
+        }
+    );
 
     // Close the auction
     contract.close(&mut app, &owner).unwrap();
 
-    // Sender1 retract funds since he did not win the auction
-    contract.retract(&mut app, &sender1, None).unwrap();
-
-    // Sender1 tries retracting again the funds even if he already retract them
-    let err = contract.retract(&mut app, &sender2, None).unwrap_err();
-
-    // Verify that the contract fails
-    assert_eq!(err, ContractError::InvalidRetract);
+    // Check the status is closed
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        state,
+        State {
+            current_status: Status::Closed,
+            highest_bid: None,
+            runner_up: None,
+            commission_pool: Uint128::zero(),
+            end: None,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
+        }
+    );
 }
 
 #[test]
-fn invalid_retract_while_open() {
+fn close_auction_after_bid() {
     // Define participant
     let owner = Addr::unchecked("owner");
-    let sender1 = Addr::unchecked("sender1");
-    let sender2 = Addr::unchecked("sender2");
+    let sender = Addr::unchecked("sender");
 
     let mut app = App::new(|router, _api, storage| {
         router
             .bank
-            .init_balance(storage, &sender1, coins(4_500_000, UATOM))
-            .unwrap();
-        router
-            .bank
-            .init_balance(storage, &sender2, coins(7_500_000, UATOM))
+            .init_balance(storage, &sender, coins(4_000_000, UATOM))
             .unwrap();
     });
 
@@ -919,41 +1187,119 @@ fn invalid_retract_while_open() {
     )
     .unwrap();
 
-    // Sender1 make a bid of 4_000_000
+    // Making a simple bid
     contract
-        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
+        .bid(&mut app, &sender, &coins(2_000_000, UATOM))
         .unwrap();
 
-    // Sender2 make a bid of 7_000_000
-    contract
-        .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
+    let bid = BIDS
+        .query(
+            &app.wrap(),
+            contract.addr().clone(),
+            (contract.id(), &sender),
+        )
         .unwrap();
 
-    // Sender2 tries retracting funds even if he won the auction
-    let err = contract.retract(&mut app, &sender2, None).unwrap_err();
+    // Check if bid is stored in the state
+    assert_eq!(bid, Some(Uint128::new(1_500_000)));
 
-    // Verify that the contract fails
-    assert_eq!(err, ContractError::OpenAcution);
-}
-// END --> Retract Tests
+    // sender should have not any balance
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        coins(2_000_000, UATOM)
+    );
 
-// START --> Query Tests
-#[test]
-fn query_total_bid() {
-    // Define participant
-    let owner = Addr::unchecked("owner");
-    let sender = Addr::unchecked("sender");
+    // owner should have got the commission
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(500_000, UATOM)
+    );
 
-    let mut app = App::new(|router, _api, storage| {
-        router
-            .bank
-            .init_balance(storage, &sender, coins(7_000_000, UATOM))
-            .unwrap();
-    });
+    // contract should store bid minus commission
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        coins(1_500_000, UATOM)
+    );
 
-    let code_id = BidwasmContract::store_code(&mut app);
+    // Check the status is open
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        state,
+        State {
+            current_status: Status::Open,
+            highest_bid: Some((sender.clone(), Uint128::new(1_500_000))),
+            runner_up: None,
+            commission_pool: Uint128::zero(),
+            end: None,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
+        }
+    );
 
-    // Instantiate contract
+    // Close the auction
+    contract.close(&mut app, &owner).unwrap();
+
+    // Check the status is closed
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        state,
+        State {
+            current_status: Status::Closed,
+            highest_bid: Some((sender.clone(), Uint128::new(1_500_000))),
+            runner_up: None,
+            commission_pool: Uint128::zero(),
+            end: None,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
+        }
+    );
+
+    // sender should have the balance minus bid and commission
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        coins(2_000_000, UATOM)
+    );
+
+    // owner should have got the commission and the bid
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(2_000_000, UATOM)
+    );
+
+    // contract should store nothing
+    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
+
+    // Try making another bid after closing the auction
+    let err = contract
+        .bid(&mut app, &sender, &coins(2_000_000, UATOM))
+        .unwrap_err();
+
+    // Verify that the contract fails if the sender bids after the acution is
+    // closed
+    assert_eq!(err, ContractError::ClosedAcution);
+}
+
+#[test]
+fn invalid_close_unauthorized() {
+    // Define participant
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract
     let contract = BidwasmContract::instantiate(
         &mut app,
         code_id,
@@ -966,35 +1312,65 @@ fn query_total_bid() {
     )
     .unwrap();
 
-    // Making a simple bid
-    contract
-        .bid(&mut app, &sender, &coins(3_500_000, UATOM))
+    // Check the status is open
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
         .unwrap();
+    assert_eq!(
+        state,
+        State {
+            current_status: Status::Open,
+            highest_bid: None,
+            runner_up: None,
+            commission_pool: Uint128::zero(),
+            end: None,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
+        }
+    );
 
-    let resp: Uint128 = app
-        .wrap()
-        .query_wasm_smart(
-            contract.addr(),
-            &QueryMsg::TotalBid {
-                address: sender.to_string(),
-            },
-        )
-        .unwrap();
+    // Try closing the auction from unauthorized account
+    let err = contract.close(&mut app, &sender).unwrap_err();
 
-    assert_eq!(resp, Uint128::new(3_000_000));
+    // Verify that the contract fails if the auction is closed by unauthorized
+    // address
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
 
-    // Making another bid
-    contract
-        .bid(&mut app, &sender, &coins(3_500_000, UATOM))
+    // Check the status is open
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
         .unwrap();
-
-    // Verify total bid for sender
-    let resp = contract.query_total_bid(&app, &sender).unwrap();
-    assert_eq!(resp, Uint128::new(6_000_000));
+    assert_eq!(
+        state,
+        State {
+            current_status: Status::Open,
+            highest_bid: None,
+            runner_up: None,
+            commission_pool: Uint128::zero(),
+            end: None,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
+        }
+    );
 }
 
+// END --> Close Tests
+
+// START --> Retract Tests
+
 #[test]
-fn query_highest_bid() {
+fn retract() {
     // Define participant
     let owner = Addr::unchecked("owner");
     let sender1 = Addr::unchecked("sender1");
@@ -1026,10 +1402,6 @@ fn query_highest_bid() {
     )
     .unwrap();
 
-    // Verify the highest_bid query returns an error if there is not any bid
-    let err = contract.query_highest_bid(&app).unwrap_err();
-    assert!(err.to_string().contains("The auction has not any bid"));
-
     // Sender1 make a bid of 4_000_000
     contract
         .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
@@ -1040,61 +1412,106 @@ fn query_highest_bid() {
         .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
         .unwrap();
 
-    // Verify highest bid query
-    let resp = contract.query_highest_bid(&app).unwrap();
-    assert_eq!(
-        resp,
-        BidResp {
-            address: sender2,
-            amount: Uint128::new(7_000_000)
-        }
-    );
-}
+    // Check that the bids are registered correctly
+    let sender1_bid = BIDS
+        .query(
+            &app.wrap(),
+            contract.addr().clone(),
+            (contract.id(), &sender1),
+        )
+        .unwrap();
 
-#[test]
-fn query_status() {
-    // Define participant
-    let owner = Addr::unchecked("owner");
+    assert_eq!(sender1_bid, Some(Uint128::new(4_000_000)));
 
-    let mut app = App::default();
+    let sender2_bid = BIDS
+        .query(
+            &app.wrap(),
+            contract.addr().clone(),
+            (contract.id(), &sender2),
+        )
+        .unwrap();
 
-    let code_id = BidwasmContract::store_code(&mut app);
+    assert_eq!(sender2_bid, Some(Uint128::new(7_000_000)));
 
-    // Instantiate contract
-    let contract = BidwasmContract::instantiate(
-        &mut app,
-        code_id,
-        &owner,
-        "Bidwasm contract",
-        None,
-        UATOM,
-        "Supercomputer #2207 bidding",
-        500_000,
-    )
-    .unwrap();
+    // senders should have not any balance
+    assert_eq!(app.wrap().query_all_balances(&sender1).unwrap(), &[]);
+    assert_eq!(app.wrap().query_all_balances(&sender2).unwrap(), &[]);
 
-    // Verify the is_closed query to check that the auction is not closed
-    let resp = contract.query_is_closed(&app).unwrap();
-    assert_eq!(resp, false);
+    // owner should have got the commission
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(1_000_000, UATOM)
+    );
+
+    // contract should store bid minus commission
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        coins(11_000_000, UATOM)
+    );
 
     // Close the auction
     contract.close(&mut app, &owner).unwrap();
 
-    // Verify the is_closed query to check that the auction is now closed
-    let resp = contract.query_is_closed(&app).unwrap();
-    assert_eq!(resp, true);
+    // Check the status is closed
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        state,
+        State {
+            current_status: Status::Closed,
+            highest_bid: Some((sender2.clone(), Uint128::new(7_000_000))),
+            runner_up: Some((sender1.clone(), Uint128::new(4_000_000))),
+            commission_pool: Uint128::zero(),
+            end: None,
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
+        }
+    );
+
+    // Sender1 retracting funds since sender did not win the auction
+    contract.retract(&mut app, &sender1, None).unwrap();
+
+    // Sender1 should have the original balance minus the commission for the
+    // bid
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(4_000_000, UATOM)
+    );
+
+    // Sender2 should have not any balance
+    assert_eq!(app.wrap().query_all_balances(&sender2).unwrap(), &[]);
+
+    // owner should have got the commission plus the highest bid at the time
+    // of the auction closure
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(8_000_000, UATOM)
+    );
+
+    // contract should have not any balance
+    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
 }
 
 #[test]
-fn query_winner() {
-    // Define participants
+fn retract_on_another_recipient() {
+    // Define participant
     let owner = Addr::unchecked("owner");
-    let sender = Addr::unchecked("sender");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    let recipient = Addr::unchecked("recipient");
 
     let mut app = App::new(|router, _api, storage| {
         router
             .bank
-            .init_balance(storage, &sender, coins(4_500_000, UATOM))
+            .init_balance(storage, &sender1, coins(4_500_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(7_500_000, UATOM))
             .unwrap();
     });
 
@@ -1115,37 +1532,61 @@ fn query_winner() {
 
     // Sender1 make a bid of 4_000_000
     contract
-        .bid(&mut app, &sender, &coins(4_500_000, UATOM))
+        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
         .unwrap();
 
-    // Verify the winner query returns an error if the auction is yet open
-    let err = contract.query_winner(&app).unwrap_err();
-    assert!(err.to_string().contains("The auction is yet open"));
+    // Sender2 make a bid of 7_000_000
+    contract
+        .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
+        .unwrap();
 
     // Close the auction
     contract.close(&mut app, &owner).unwrap();
 
-    // Verify the is_closed query to check that the auction is now closed
-    let resp = contract.query_winner(&app).unwrap();
+    // Sender1 retracting funds since sender did not win the auction
+    contract.retract(&mut app, &sender1, &recipient).unwrap();
+
+    // Recipient should have the original balance minus the commission for the
+    // bid
     assert_eq!(
-        resp,
-        BidResp {
-            address: sender,
-            amount: Uint128::new(4_000_000)
-        }
+        app.wrap().query_all_balances(&recipient).unwrap(),
+        coins(4_000_000, UATOM)
+    );
+
+    // Sender1 bid has been consumed by the retract
+    let err = contract.retract(&mut app, &sender1, None).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRetract);
+
+    // Sender1 and Sender2 should have not any balance
+    assert_eq!(app.wrap().query_all_balances(&sender1).unwrap(), &[]);
+    assert_eq!(app.wrap().query_all_balances(&sender2).unwrap(), &[]);
+
+    // owner should have got the commission plus the highest bid at the time
+    // of the auction closure
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(8_000_000, UATOM)
     );
+
+    // contract should have not any balance
+    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
 }
 
 #[test]
-fn query_no_winner() {
-    // Define participants
+fn invalid_retract_by_winner() {
+    // Define participant
     let owner = Addr::unchecked("owner");
-    let sender = Addr::unchecked("sender");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
 
     let mut app = App::new(|router, _api, storage| {
         router
             .bank
-            .init_balance(storage, &sender, coins(4_500_000, UATOM))
+            .init_balance(storage, &sender1, coins(4_500_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(7_500_000, UATOM))
             .unwrap();
     });
 
@@ -1164,14 +1605,4233 @@ fn query_no_winner() {
     )
     .unwrap();
 
-    // Close the auction
+    // Sender1 make a bid of 4_000_000
+    contract
+        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
+        .unwrap();
+
+    // Sender2 make a bid of 7_000_000
+    contract
+        .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
+        .unwrap();
+
+    // Close the auction
     contract.close(&mut app, &owner).unwrap();
 
-    // Verify the winner query returns an error if the auction has not any bid
-    let err = contract.query_winner(&app).unwrap_err();
+    // Sender2 tries retracting funds even if he won the auction
+    let err = contract.retract(&mut app, &sender2, None).unwrap_err();
+
+    // Verify that the contract fails
+    assert_eq!(err, ContractError::InvalidRetract);
+}
+
+#[test]
+fn invalid_double_retract() {
+    // Define participant
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(4_500_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(7_500_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract
+    let contract = BidwasmContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        None,
+        UATOM,
+        "Supercomputer #2207 bidding",
+        500_000,
+    )
+    .unwrap();
+
+    // Sender1 make a bid of 4_000_000
+    contract
+        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
+        .unwrap();
+
+    // Sender2 make a bid of 7_000_000
+    contract
+        .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
+        .unwrap();
+
+    // Close the auction
+    contract.close(&mut app, &owner).unwrap();
+
+    // Sender1 retract funds since he did not win the auction
+    contract.retract(&mut app, &sender1, None).unwrap();
+
+    // Sender1 tries retracting again the funds even if he already retract them
+    let err = contract.retract(&mut app, &sender2, None).unwrap_err();
+
+    // Verify that the contract fails
+    assert_eq!(err, ContractError::InvalidRetract);
+}
+
+#[test]
+fn invalid_retract_while_open() {
+    // Define participant
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(4_500_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(7_500_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract
+    let contract = BidwasmContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        None,
+        UATOM,
+        "Supercomputer #2207 bidding",
+        500_000,
+    )
+    .unwrap();
+
+    // Sender1 make a bid of 4_000_000
+    contract
+        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
+        .unwrap();
+
+    // Sender2 make a bid of 7_000_000
+    contract
+        .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
+        .unwrap();
+
+    // Sender2 tries retracting funds even if he won the auction
+    let err = contract.retract(&mut app, &sender2, None).unwrap_err();
+
+    // Verify that the contract fails
+    assert_eq!(err, ContractError::OpenAcution);
+}
+// END --> Retract Tests
+
+// START --> Query Tests
+#[test]
+fn query_total_bid() {
+    // Define participant
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(7_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract
+    let contract = BidwasmContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        None,
+        UATOM,
+        "Supercomputer #2207 bidding",
+        500_000,
+    )
+    .unwrap();
+
+    // Making a simple bid
+    contract
+        .bid(&mut app, &sender, &coins(3_500_000, UATOM))
+        .unwrap();
+
+    let resp: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            contract.addr(),
+            &QueryMsg::TotalBid {
+                auction_id: contract.id(),
+                address: sender.to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp, Uint128::new(3_000_000));
+
+    // Making another bid
+    contract
+        .bid(&mut app, &sender, &coins(3_500_000, UATOM))
+        .unwrap();
+
+    // Verify total bid for sender
+    let resp = contract.query_total_bid(&app, &sender).unwrap();
+    assert_eq!(resp, Uint128::new(6_000_000));
+}
+
+#[test]
+fn query_highest_bid() {
+    // Define participant
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(4_500_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(7_500_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract
+    let contract = BidwasmContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        None,
+        UATOM,
+        "Supercomputer #2207 bidding",
+        500_000,
+    )
+    .unwrap();
+
+    // Verify the highest_bid query returns an error if there is not any bid
+    let err = contract.query_highest_bid(&app).unwrap_err();
     assert!(err.to_string().contains("The auction has not any bid"));
+
+    // Sender1 make a bid of 4_000_000
+    contract
+        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
+        .unwrap();
+
+    // Sender2 make a bid of 7_000_000
+    contract
+        .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
+        .unwrap();
+
+    // Verify highest bid query
+    let resp = contract.query_highest_bid(&app).unwrap();
+    assert_eq!(
+        resp,
+        BidResp {
+            address: sender2,
+            amount: Uint128::new(7_000_000)
+        }
+    );
 }
-// END --> Query Tests
+
+#[test]
+fn query_status() {
+    // Define participant
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract
+    let contract = BidwasmContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        None,
+        UATOM,
+        "Supercomputer #2207 bidding",
+        500_000,
+    )
+    .unwrap();
+
+    // Verify the is_closed query to check that the auction is not closed
+    let resp = contract.query_is_closed(&app).unwrap();
+    assert!(!resp);
+
+    // Close the auction
+    contract.close(&mut app, &owner).unwrap();
+
+    // Verify the is_closed query to check that the auction is now closed
+    let resp = contract.query_is_closed(&app).unwrap();
+    assert!(resp);
+}
+
+#[test]
+fn query_winner() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(4_500_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract
+    let contract = BidwasmContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        None,
+        UATOM,
+        "Supercomputer #2207 bidding",
+        500_000,
+    )
+    .unwrap();
+
+    // Sender1 make a bid of 4_000_000
+    contract
+        .bid(&mut app, &sender, &coins(4_500_000, UATOM))
+        .unwrap();
+
+    // Verify the winner query returns an error if the auction is yet open
+    let err = contract.query_winner(&app).unwrap_err();
+    assert!(err.to_string().contains("The auction is yet open"));
+
+    // Close the auction
+    contract.close(&mut app, &owner).unwrap();
+
+    // Verify the is_closed query to check that the auction is now closed
+    let resp = contract.query_winner(&app).unwrap();
+    assert_eq!(
+        resp,
+        WinnerResp::Won {
+            address: sender,
+            amount: Uint128::new(4_000_000),
+            clearing_price: Uint128::new(4_000_000),
+            bought_now: false,
+        }
+    );
+}
+
+#[test]
+fn query_no_winner() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(4_500_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract
+    let contract = BidwasmContract::instantiate(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        None,
+        UATOM,
+        "Supercomputer #2207 bidding",
+        500_000,
+    )
+    .unwrap();
+
+    // Close the auction
+    contract.close(&mut app, &owner).unwrap();
+
+    // Verify the winner query returns an error if the auction has not any bid
+    let err = contract.query_winner(&app).unwrap_err();
+    assert!(err.to_string().contains("The auction has not any bid"));
+}
+// END --> Query Tests
+
+// START --> Reserve Tests
+
+#[test]
+fn reserve_not_met() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(4_000_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(7_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract with a reserve price of 10_000_000
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            reserve_price: Some(Uint128::new(10_000_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender1, &coins(4_000_000, UATOM))
+        .unwrap();
+    contract
+        .bid(&mut app, &sender2, &coins(7_000_000, UATOM))
+        .unwrap();
+
+    // Close the auction: the highest bid does not reach the reserve price
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(app.wrap().query_all_balances(&owner).unwrap(), &[]);
+
+    // Nobody won the auction
+    assert_eq!(
+        contract.query_winner(&app).unwrap(),
+        WinnerResp::ReserveNotMet {
+            address: sender2.clone(),
+            amount: Uint128::new(7_000_000),
+            reserve_price: Uint128::new(10_000_000),
+        }
+    );
+
+    // Every bidder, including the top one, gets the funds back
+    contract.retract(&mut app, &sender1, None).unwrap();
+    contract.retract(&mut app, &sender2, None).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(4_000_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&sender2).unwrap(),
+        coins(7_000_000, UATOM)
+    );
+    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
+}
+
+#[test]
+fn reserve_met() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(10_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract with a reserve price of 10_000_000
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            reserve_price: Some(Uint128::new(10_000_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // A bid exactly at the reserve price wins
+    contract
+        .bid(&mut app, &sender, &coins(10_000_000, UATOM))
+        .unwrap();
+    contract.close(&mut app, &owner).unwrap();
+
+    assert_eq!(
+        contract.query_winner(&app).unwrap(),
+        WinnerResp::Won {
+            address: sender.clone(),
+            amount: Uint128::new(10_000_000),
+            clearing_price: Uint128::new(10_000_000),
+            bought_now: false,
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(10_000_000, UATOM)
+    );
+
+    let err = contract.retract(&mut app, &sender, None).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRetract);
+}
+// END --> Reserve Tests
+
+// START --> Schedule Tests
+
+#[test]
+fn invalid_schedule() {
+    // Define participant
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+    let now = app.block_info().time;
+
+    // An auction cannot end before it starts
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            start: Some(Scheduled::AtTime(now.plus_seconds(200))),
+            end: Some(Scheduled::AtTime(now.plus_seconds(100))),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSchedule);
+
+    // An auction cannot end in the past
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            end: Some(Scheduled::AtTime(now)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSchedule);
+}
+
+#[test]
+fn bid_before_start() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(4_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+    let start = Scheduled::AtTime(app.block_info().time.plus_seconds(100));
+
+    // Instantiate contract starting in 100 seconds
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            start: Some(start),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // Bidding before the start fails
+    let err = contract
+        .bid(&mut app, &sender, &coins(2_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotStartedAuction { start });
+
+    // Once the start is reached, bids are accepted
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    contract
+        .bid(&mut app, &sender, &coins(2_000_000, UATOM))
+        .unwrap();
+
+    let resp = contract.query_total_bid(&app, &sender).unwrap();
+    assert_eq!(resp, Uint128::new(2_000_000));
+}
+
+#[test]
+fn auction_closed_by_end() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(4_000_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(7_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+    let end = app.block_info().height + 10;
+
+    // Instantiate contract ending in 10 blocks
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            end: Some(Scheduled::AtHeight(end)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender1, &coins(4_000_000, UATOM))
+        .unwrap();
+    contract
+        .bid(&mut app, &sender2, &coins(5_000_000, UATOM))
+        .unwrap();
+
+    // Nothing can be retracted while the auction is running
+    let err = contract.retract(&mut app, &sender1, None).unwrap_err();
+    assert_eq!(err, ContractError::OpenAcution);
+    assert!(!contract.query_is_closed(&app).unwrap());
+
+    // Reach the end of the auction
+    app.update_block(|block| block.height = end);
+
+    // The auction counts as closed even if nobody closed it
+    assert!(contract.query_is_closed(&app).unwrap());
+    assert_eq!(
+        contract.query_winner(&app).unwrap(),
+        WinnerResp::Won {
+            address: sender2.clone(),
+            amount: Uint128::new(5_000_000),
+            clearing_price: Uint128::new(5_000_000),
+            bought_now: false,
+        }
+    );
+
+    // Bids are no longer accepted
+    let err = contract
+        .bid(&mut app, &sender2, &coins(2_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::ClosedAcution);
+
+    // The winner cannot retract the winning bid
+    let err = contract.retract(&mut app, &sender2, None).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRetract);
+
+    // The loser can retract right away
+    contract.retract(&mut app, &sender1, None).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(4_000_000, UATOM)
+    );
+
+    // The owner still closes the auction to collect the winning bid
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(5_000_000, UATOM)
+    );
+    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
+}
+#[test]
+fn invalid_soft_close() {
+    // Define participant
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+    let end = app.block_info().height + 100;
+
+    // Soft close cannot extend a height based end
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            end: Some(Scheduled::AtHeight(end)),
+            soft_close: Some(SoftClose {
+                window: 300,
+                extension: 300,
+                max_extension: 900,
+            }),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSoftClose);
+}
+
+#[test]
+fn soft_close_extends_end() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(10_000_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(10_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+    let start = app.block_info().time;
+    let end = start.plus_seconds(3_600);
+
+    // Bids in the last 5 minutes extend the end by 5 minutes, up to 8 minutes
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            end: Some(Scheduled::AtTime(end)),
+            soft_close: Some(SoftClose {
+                window: 300,
+                extension: 300,
+                max_extension: 480,
+            }),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // A bid far from the end does not extend it
+    contract
+        .bid(&mut app, &sender1, &coins(1_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_schedule(&app).unwrap(),
+        ScheduleResp {
+            start: None,
+            end: Some(Scheduled::AtTime(end)),
+            extension: 0,
+        }
+    );
+
+    // A bid in the last minute moves the end forward by 5 minutes
+    app.update_block(|block| block.time = end.minus_seconds(60));
+    contract
+        .bid(&mut app, &sender2, &coins(2_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_schedule(&app).unwrap(),
+        ScheduleResp {
+            start: None,
+            end: Some(Scheduled::AtTime(end.plus_seconds(300))),
+            extension: 300,
+        }
+    );
+
+    // The original end has passed, but the auction is still open
+    app.update_block(|block| block.time = end.plus_seconds(200));
+    assert!(!contract.query_is_closed(&app).unwrap());
+
+    // Another late bid only extends the end up to the maximum extension
+    contract
+        .bid(&mut app, &sender1, &coins(2_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_schedule(&app).unwrap(),
+        ScheduleResp {
+            start: None,
+            end: Some(Scheduled::AtTime(end.plus_seconds(480))),
+            extension: 480,
+        }
+    );
+
+    // Once the maximum extension is reached, late bids do not extend the end
+    app.update_block(|block| block.time = end.plus_seconds(470));
+    contract
+        .bid(&mut app, &sender2, &coins(2_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_schedule(&app).unwrap().end,
+        Some(Scheduled::AtTime(end.plus_seconds(480)))
+    );
+
+    app.update_block(|block| block.time = end.plus_seconds(480));
+    assert!(contract.query_is_closed(&app).unwrap());
+    let err = contract
+        .bid(&mut app, &sender1, &coins(2_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::ClosedAcution);
+}
+// END --> Schedule Tests
+
+// START --> Settle Tests
+
+#[test]
+fn settle_after_end() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    let keeper = Addr::unchecked("keeper");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(4_500_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(7_500_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+    let end = app.block_info().time.plus_seconds(3_600);
+
+    // Instantiate contract keeping 600_000 of the commissions for the keeper
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Flat(Uint128::new(500_000))),
+            end: Some(Scheduled::AtTime(end)),
+            keeper_reward: Some(Uint128::new(600_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
+        .unwrap();
+    contract
+        .bid(&mut app, &sender2, &coins(7_500_000, UATOM))
+        .unwrap();
+
+    // The first commission and part of the second one are set aside
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(400_000, UATOM)
+    );
+
+    // Nobody can settle before the end
+    let err = contract.settle(&mut app, &keeper).unwrap_err();
+    assert_eq!(err, ContractError::OpenAcution);
+
+    // Anybody can settle after the end
+    app.update_block(|block| block.time = end);
+    contract.settle(&mut app, &keeper).unwrap();
+
+    // The owner gets the winning bid and the keeper gets its reward
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(7_400_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&keeper).unwrap(),
+        coins(600_000, UATOM)
+    );
+
+    let state = STATES
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        state,
+        State {
+            current_status: Status::Closed,
+            highest_bid: Some((sender2.clone(), Uint128::new(7_000_000))),
+            runner_up: Some((sender1.clone(), Uint128::new(4_000_000))),
+            commission_pool: Uint128::zero(),
+            end: Some(Scheduled::AtTime(end)),
+            extension: 0,
+            unrevealed_deposits: Uint128::zero(),
+            unit_bids: 0,
+            bought_now: false,
+        }
+    );
+
+    // The auction cannot be settled or closed twice
+    let err = contract.settle(&mut app, &keeper).unwrap_err();
+    assert_eq!(err, ContractError::ClosedAcution);
+    let err = contract.close(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::ClosedAcution);
+
+    // The loser retracts the bid
+    contract.retract(&mut app, &sender1, None).unwrap();
+    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
+}
+
+#[test]
+fn close_returns_keeper_reward_to_owner() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(4_500_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract keeping 600_000 of the commissions for the keeper
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Flat(Uint128::new(500_000))),
+            keeper_reward: Some(Uint128::new(600_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender, &coins(4_500_000, UATOM))
+        .unwrap();
+    assert_eq!(app.wrap().query_all_balances(&owner).unwrap(), &[]);
+
+    // Closing gives the owner both the winning bid and the set aside commission
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(4_500_000, UATOM)
+    );
+    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
+}
+// END --> Settle Tests
+
+// START --> CW20 Tests
+
+// Store the cw20-base code and instantiate a token with the given balances
+fn instantiate_cw20(app: &mut App, balances: &[(&Addr, u128)]) -> Addr {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    let code_id = app.store_code(Box::new(contract));
+
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked("minter"),
+        &Cw20InstantiateMsg {
+            name: "Bidding token".to_string(),
+            symbol: "BID".to_string(),
+            decimals: 6,
+            initial_balances: balances
+                .iter()
+                .map(|(address, amount)| Cw20Coin {
+                    address: address.to_string(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+            mint: None,
+            marketing: None,
+        },
+        &[],
+        "Bidding token",
+        None,
+    )
+    .unwrap()
+}
+
+fn cw20_balance(app: &App, token: &Addr, address: &Addr) -> Uint128 {
+    let resp: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    resp.balance
+}
+
+#[test]
+fn cw20_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(1_000_000, UATOM))
+            .unwrap();
+    });
+
+    let token = instantiate_cw20(&mut app, &[(&sender1, 4_500_000), (&sender2, 7_500_000)]);
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract priced in the CW20 token
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            denom: UncheckedDenom::Cw20(token.to_string()),
+            commission: Some(CommissionPolicy::Flat(Uint128::new(500_000))),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    let config = CONFIGS
+        .query(&app.wrap(), contract.addr().clone(), contract.id())
+        .unwrap()
+        .unwrap();
+    assert_eq!(config.denom, Denom::Cw20(token.clone()));
+
+    // Native tokens cannot be used to bid
+    let err = contract
+        .bid(&mut app, &sender1, &coins(1_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds);
+
+    // Bids are placed sending CW20 tokens to the contract
+    contract
+        .bid_cw20(&mut app, &sender1, &token, 4_500_000)
+        .unwrap();
+    contract
+        .bid_cw20(&mut app, &sender2, &token, 7_500_000)
+        .unwrap();
+
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        BidResp {
+            address: sender2.clone(),
+            amount: Uint128::new(7_000_000)
+        }
+    );
+
+    // Commissions are paid in CW20 tokens
+    assert_eq!(cw20_balance(&app, &token, &owner), Uint128::new(1_000_000));
+    assert_eq!(
+        cw20_balance(&app, &token, contract.addr()),
+        Uint128::new(11_000_000)
+    );
+
+    // Close the auction and retract the losing bid
+    contract.close(&mut app, &owner).unwrap();
+    contract.retract(&mut app, &sender1, None).unwrap();
+
+    assert_eq!(cw20_balance(&app, &token, &owner), Uint128::new(8_000_000));
+    assert_eq!(
+        cw20_balance(&app, &token, &sender1),
+        Uint128::new(4_000_000)
+    );
+    assert_eq!(cw20_balance(&app, &token, contract.addr()), Uint128::zero());
+}
+
+#[test]
+fn cw20_invalid_token() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::default();
+
+    let token = instantiate_cw20(&mut app, &[(&sender, 1_000_000)]);
+    let other_token = instantiate_cw20(&mut app, &[(&sender, 1_000_000)]);
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract priced in the CW20 token
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            denom: UncheckedDenom::Cw20(token.to_string()),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // Only the auction token is accepted
+    let err = contract
+        .bid_cw20(&mut app, &sender, &other_token, 1_000_000)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidToken {
+            token: other_token.to_string()
+        }
+    );
+    assert_eq!(
+        cw20_balance(&app, &other_token, &sender),
+        Uint128::new(1_000_000)
+    );
+}
+// END --> CW20 Tests
+
+// START --> NFT Lot Tests
+
+// Store the cw721-base code and mint a token to the given owner
+fn instantiate_cw721(app: &mut App, owner: &Addr, token_id: &str) -> Addr {
+    let contract = ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    );
+    let code_id = app.store_code(Box::new(contract));
+
+    let minter = Addr::unchecked("minter");
+    let collection = app
+        .instantiate_contract(
+            code_id,
+            minter.clone(),
+            &Cw721InstantiateMsg {
+                name: "Supercomputers".to_string(),
+                symbol: "SC".to_string(),
+                minter: minter.to_string(),
+            },
+            &[],
+            "Supercomputers",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        minter,
+        collection.clone(),
+        &Cw721BaseExecuteMsg::<Extension, Empty>::Mint(MintMsg {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: None,
+        }),
+        &[],
+    )
+    .unwrap();
+
+    collection
+}
+
+fn nft_owner(app: &App, collection: &Addr, token_id: &str) -> String {
+    let resp: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    resp.owner
+}
+
+#[test]
+fn nft_lot_to_winner() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(4_000_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(7_000_000, UATOM))
+            .unwrap();
+    });
+
+    let collection = instantiate_cw721(&mut app, &owner, "2207");
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract selling the NFT
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            lot: Some(UncheckedLot::Nft {
+                collection: collection.to_string(),
+                token_id: "2207".to_string(),
+            }),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    let lot = Lot::Nft {
+        collection: collection.clone(),
+        token_id: "2207".to_string(),
+    };
+    assert_eq!(
+        contract.query_lot(&app).unwrap(),
+        LotResp {
+            lot: Some(lot.clone()),
+            escrowed: false
+        }
+    );
+
+    // Bids are rejected until the lot is deposited
+    let err = contract
+        .bid(&mut app, &sender1, &coins(4_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::PendingAuction);
+
+    // Depositing the NFT opens the auction
+    contract
+        .deposit_nft(&mut app, &owner, &collection, "2207")
+        .unwrap();
+    assert_eq!(
+        nft_owner(&app, &collection, "2207"),
+        contract.addr().as_str()
+    );
+    assert_eq!(
+        contract.query_lot(&app).unwrap(),
+        LotResp {
+            lot: Some(lot.clone()),
+            escrowed: true
+        }
+    );
+
+    contract
+        .bid(&mut app, &sender1, &coins(4_000_000, UATOM))
+        .unwrap();
+    contract
+        .bid(&mut app, &sender2, &coins(7_000_000, UATOM))
+        .unwrap();
+
+    // Closing the auction delivers the NFT to the winner
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(nft_owner(&app, &collection, "2207"), sender2.as_str());
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(7_000_000, UATOM)
+    );
+    assert_eq!(
+        contract.query_lot(&app).unwrap(),
+        LotResp {
+            lot: Some(lot),
+            escrowed: false
+        }
+    );
+}
+
+#[test]
+fn nft_lot_back_to_owner() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let collection = instantiate_cw721(&mut app, &owner, "2207");
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract selling the NFT
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            lot: Some(UncheckedLot::Nft {
+                collection: collection.to_string(),
+                token_id: "2207".to_string(),
+            }),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .deposit_nft(&mut app, &owner, &collection, "2207")
+        .unwrap();
+
+    // Without bids, the NFT goes back to the owner
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(nft_owner(&app, &collection, "2207"), owner.as_str());
+}
+
+#[test]
+fn invalid_nft_deposit() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::default();
+
+    let collection = instantiate_cw721(&mut app, &owner, "2207");
+    let other_collection = instantiate_cw721(&mut app, &sender, "2207");
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract selling the NFT
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            lot: Some(UncheckedLot::Nft {
+                collection: collection.to_string(),
+                token_id: "2207".to_string(),
+            }),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // A token from another collection is not the lot
+    let err = contract
+        .deposit_nft(&mut app, &sender, &other_collection, "2207")
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidLot);
+    assert_eq!(nft_owner(&app, &other_collection, "2207"), sender.as_str());
+}
+// END --> NFT Lot Tests
+
+// START --> Fungible Lot Tests
+#[test]
+fn native_lot_to_winner() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &owner, coins(1_000_000, "ugov"))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender, coins(5_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    let msg = InstantiateMsg {
+        lot: Some(UncheckedLot::Native(coins(1_000_000, "ugov"))),
+        ..base_msg(&owner)
+    };
+
+    // The lot must be sent along with the instantiation
+    let err =
+        BidwasmContract::instantiate_with_msg(&mut app, code_id, &owner, "Bidwasm contract", &msg)
+            .unwrap_err();
+    assert_eq!(err, ContractError::InvalidLot);
+
+    let err = BidwasmContract::instantiate_with_funds(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &msg,
+        &coins(500_000, "ugov"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidLot);
+
+    let contract = BidwasmContract::instantiate_with_funds(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &msg,
+        &coins(1_000_000, "ugov"),
+    )
+    .unwrap();
+
+    // Native coins are escrowed right away, so the auction is open
+    assert_eq!(
+        contract.query_lot(&app).unwrap(),
+        LotResp {
+            lot: Some(Lot::Native(coins(1_000_000, "ugov"))),
+            escrowed: true
+        }
+    );
+
+    contract
+        .bid(&mut app, &sender, &coins(5_000_000, UATOM))
+        .unwrap();
+
+    // Closing the auction delivers the coins to the winner
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        coins(1_000_000, "ugov")
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(5_000_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        vec![]
+    );
+}
+
+#[test]
+fn cw20_lot_to_winner() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(5_000_000, UATOM))
+            .unwrap();
+    });
+
+    let token = instantiate_cw20(&mut app, &[(&owner, 1_000_000), (&sender, 1_000_000)]);
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract selling CW20 tokens
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            lot: Some(UncheckedLot::Cw20 {
+                token: token.to_string(),
+                amount: Uint128::new(1_000_000),
+            }),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // Bids are rejected until the lot is deposited
+    let err = contract
+        .bid(&mut app, &sender, &coins(5_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::PendingAuction);
+
+    // Only the exact amount can be deposited, and only by the owner
+    let err = contract
+        .deposit_cw20(&mut app, &owner, &token, 500_000)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidLot);
+
+    let err = contract
+        .deposit_cw20(&mut app, &sender, &token, 1_000_000)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    contract
+        .deposit_cw20(&mut app, &owner, &token, 1_000_000)
+        .unwrap();
+    assert_eq!(
+        cw20_balance(&app, &token, contract.addr()),
+        Uint128::new(1_000_000)
+    );
+    assert_eq!(
+        contract.query_lot(&app).unwrap(),
+        LotResp {
+            lot: Some(Lot::Cw20 {
+                token: token.clone(),
+                amount: Uint128::new(1_000_000)
+            }),
+            escrowed: true
+        }
+    );
+
+    contract
+        .bid(&mut app, &sender, &coins(5_000_000, UATOM))
+        .unwrap();
+
+    // Closing the auction delivers the tokens to the winner
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(cw20_balance(&app, &token, &sender), Uint128::new(2_000_000));
+    assert_eq!(cw20_balance(&app, &token, contract.addr()), Uint128::zero());
+}
+
+#[test]
+fn cw20_lot_back_to_owner() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(5_000_000, UATOM))
+            .unwrap();
+    });
+
+    let token = instantiate_cw20(&mut app, &[(&owner, 1_000_000)]);
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract selling CW20 tokens with a reserve price
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            lot: Some(UncheckedLot::Cw20 {
+                token: token.to_string(),
+                amount: Uint128::new(1_000_000),
+            }),
+            reserve_price: Some(Uint128::new(10_000_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .deposit_cw20(&mut app, &owner, &token, 1_000_000)
+        .unwrap();
+    contract
+        .bid(&mut app, &sender, &coins(5_000_000, UATOM))
+        .unwrap();
+
+    // The reserve price is not met, so the tokens go back to the owner
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(cw20_balance(&app, &token, &owner), Uint128::new(1_000_000));
+    assert_eq!(cw20_balance(&app, &token, &sender), Uint128::zero());
+
+    // And the bid can be retracted
+    contract.retract(&mut app, &sender, None).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        coins(5_000_000, UATOM)
+    );
+}
+// END --> Fungible Lot Tests
+
+// START --> Auction House Tests
+#[test]
+fn independent_auctions() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let seller = Addr::unchecked("seller");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(10_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // The instantiation creates the first auction
+    let first = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &base_msg(&owner),
+    )
+    .unwrap();
+    assert_eq!(first.id(), 0);
+
+    // Anyone can create another auction in the same contract, owned by its
+    // creator
+    let second = first
+        .create_auction(
+            &mut app,
+            &seller,
+            &InstantiateMsg {
+                owner: None,
+                description: "Supercomputer #2208 bidding".to_string(),
+                ..base_msg(&owner)
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(second.id(), 1);
+    assert_eq!(second.addr(), first.addr());
+
+    // Bids are tracked per auction
+    first
+        .bid(&mut app, &sender, &coins(3_000_000, UATOM))
+        .unwrap();
+    second
+        .bid(&mut app, &sender, &coins(5_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        first.query_total_bid(&app, &sender).unwrap(),
+        Uint128::new(3_000_000)
+    );
+    assert_eq!(
+        second.query_total_bid(&app, &sender).unwrap(),
+        Uint128::new(5_000_000)
+    );
+
+    // Each auction is closed by its own owner
+    let err = second.close(&mut app, &owner).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: seller.to_string()
+        }
+    );
+    second.close(&mut app, &seller).unwrap();
+    assert!(second.query_is_closed(&app).unwrap());
+    assert!(!first.query_is_closed(&app).unwrap());
+    assert_eq!(
+        app.wrap().query_all_balances(&seller).unwrap(),
+        coins(5_000_000, UATOM)
+    );
+
+    // The first auction still accepts bids
+    first
+        .bid(&mut app, &sender, &coins(2_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        first.query_highest_bid(&app).unwrap(),
+        BidResp {
+            address: sender,
+            amount: Uint128::new(5_000_000)
+        }
+    );
+}
+
+#[test]
+fn list_auctions() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &base_msg(&owner),
+    )
+    .unwrap();
+    for _ in 0..3 {
+        contract
+            .create_auction(&mut app, &owner, &base_msg(&owner), &[])
+            .unwrap();
+    }
+    contract.close(&mut app, &owner).unwrap();
+
+    let auction = |id, status| AuctionResp {
+        id,
+        owner: owner.clone(),
+        description: "Supercomputer #2207 bidding".to_string(),
+        status,
+    };
+
+    // Auctions are listed by id, a page at a time
+    assert_eq!(
+        contract.query_list_auctions(&app, None, None, 2).unwrap(),
+        ListAuctionsResp {
+            auctions: vec![auction(0, Status::Closed), auction(1, Status::Open)]
+        }
+    );
+    assert_eq!(
+        contract.query_list_auctions(&app, None, 1, 2).unwrap(),
+        ListAuctionsResp {
+            auctions: vec![auction(2, Status::Open), auction(3, Status::Open)]
+        }
+    );
+
+    // And can be filtered by status
+    assert_eq!(
+        contract
+            .query_list_auctions(&app, Status::Open, None, None)
+            .unwrap(),
+        ListAuctionsResp {
+            auctions: vec![
+                auction(1, Status::Open),
+                auction(2, Status::Open),
+                auction(3, Status::Open)
+            ]
+        }
+    );
+    assert_eq!(
+        contract
+            .query_list_auctions(&app, Status::Closed, 0, None)
+            .unwrap(),
+        ListAuctionsResp { auctions: vec![] }
+    );
+}
+// END --> Auction House Tests
+
+// START --> Factory Tests
+#[test]
+fn factory_registry() {
+    // Define participants
+    let admin = Addr::unchecked("admin");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+    let factory_code_id = FactoryContract::store_code(&mut app);
+
+    let factory = FactoryContract::instantiate(
+        &mut app,
+        factory_code_id,
+        &admin,
+        "Bidwasm factory",
+        code_id,
+    )
+    .unwrap();
+    assert_eq!(factory.query_config(&app).unwrap(), ConfigResp { code_id });
+
+    // Auctions are owned by their creator unless stated otherwise
+    let first = factory
+        .create_auction(
+            &mut app,
+            &alice,
+            &InstantiateMsg {
+                owner: None,
+                ..base_msg(&alice)
+            },
+            &[],
+        )
+        .unwrap();
+    let first_created_at = app.block_info().time;
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    let second = factory
+        .create_auction(&mut app, &alice, &base_msg(&bob), &[])
+        .unwrap();
+    let second_created_at = app.block_info().time;
+
+    let first_contract = BidwasmContract::from(first.clone());
+    first_contract.close(&mut app, &alice).unwrap();
+
+    let record = |id, address: &Addr, owner: &Addr, created_at, status| AuctionRecordResp {
+        id,
+        address: address.clone(),
+        owner: owner.clone(),
+        created_at,
+        status,
+    };
+
+    // Every auction is registered with its owner, creation time and status
+    assert_eq!(
+        factory
+            .query_auctions(&app, None, None, None, None, None, None)
+            .unwrap(),
+        AuctionsResp {
+            auctions: vec![
+                record(0, &first, &alice, first_created_at, Status::Closed),
+                record(1, &second, &bob, second_created_at, Status::Open),
+            ],
+            next_start_after: None,
+        }
+    );
+
+    // And can be filtered by owner, status and creation time
+    let expected = AuctionsResp {
+        auctions: vec![record(1, &second, &bob, second_created_at, Status::Open)],
+        next_start_after: None,
+    };
+    assert_eq!(
+        factory
+            .query_auctions(&app, &bob, None, None, None, None, None)
+            .unwrap(),
+        expected
+    );
+    assert_eq!(
+        factory
+            .query_auctions(&app, None, Status::Open, None, None, None, None)
+            .unwrap(),
+        expected
+    );
+    assert_eq!(
+        factory
+            .query_auctions(&app, None, None, first_created_at, None, None, None)
+            .unwrap(),
+        expected
+    );
+    assert_eq!(
+        factory
+            .query_auctions(&app, None, None, None, second_created_at, None, None)
+            .unwrap(),
+        AuctionsResp {
+            auctions: vec![record(0, &first, &alice, first_created_at, Status::Closed)],
+            next_start_after: None,
+        }
+    );
+    assert_eq!(
+        factory
+            .query_auctions(&app, None, None, None, None, 0, None)
+            .unwrap(),
+        expected
+    );
+
+    // A full page tells where to resume
+    assert_eq!(
+        factory
+            .query_auctions(&app, None, None, None, None, None, 1)
+            .unwrap(),
+        AuctionsResp {
+            auctions: vec![record(0, &first, &alice, first_created_at, Status::Closed)],
+            next_start_after: Some(0),
+        }
+    );
+}
+
+#[test]
+fn factory_auction_with_native_lot() {
+    // Define participants
+    let admin = Addr::unchecked("admin");
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &owner, coins(1_000_000, "ugov"))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+    let factory_code_id = FactoryContract::store_code(&mut app);
+
+    let factory = FactoryContract::instantiate(
+        &mut app,
+        factory_code_id,
+        &admin,
+        "Bidwasm factory",
+        code_id,
+    )
+    .unwrap();
+
+    // Funds are forwarded to the new auction
+    let auction = factory
+        .create_auction(
+            &mut app,
+            &owner,
+            &InstantiateMsg {
+                lot: Some(UncheckedLot::Native(coins(1_000_000, "ugov"))),
+                ..base_msg(&owner)
+            },
+            &coins(1_000_000, "ugov"),
+        )
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&auction).unwrap(),
+        coins(1_000_000, "ugov")
+    );
+
+    // A failing instantiation creates no auction
+    let err = factory
+        .create_auction(
+            &mut app,
+            &owner,
+            &InstantiateMsg {
+                lot: Some(UncheckedLot::Native(coins(1_000_000, "ugov"))),
+                ..base_msg(&owner)
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidLot);
+    assert_eq!(
+        factory
+            .query_auctions(&app, None, None, None, None, None, None)
+            .unwrap()
+            .auctions
+            .len(),
+        1
+    );
+}
+// END --> Factory Tests
+
+// START --> Dutch Auction Tests
+#[test]
+fn dutch_auction_linear() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(5_000_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &sender2, coins(10_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract with a price falling from 10 to 2 atoms in 800s
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Flat(Uint128::new(500_000))),
+            kind: Some(AuctionKind::Dutch(DutchAuction {
+                start_price: Uint128::new(10_000_000),
+                floor_price: Uint128::new(2_000_000),
+                decline: Decline::Linear { duration: 800 },
+            })),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        contract.query_current_price(&app).unwrap(),
+        Uint128::new(10_000_000)
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(400));
+    assert_eq!(
+        contract.query_current_price(&app).unwrap(),
+        Uint128::new(6_000_000)
+    );
+
+    // The funds must cover the price and its commission
+    let err = contract
+        .bid(&mut app, &sender1, &coins(5_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BelowCurrentPrice {
+            funds: Uint128::new(5_000_000),
+            price: Uint128::new(6_000_000),
+            commission: Uint128::new(500_000)
+        }
+    );
+
+    // The first bid covering the price wins and the overpayment is refunded
+    contract
+        .bid(&mut app, &sender2, &coins(10_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender2).unwrap(),
+        coins(3_500_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(6_500_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        vec![]
+    );
+
+    // The auction is closed right away
+    assert!(contract.query_is_closed(&app).unwrap());
+    assert_eq!(
+        contract.query_winner(&app).unwrap(),
+        WinnerResp::Won {
+            address: sender2,
+            amount: Uint128::new(6_000_000),
+            clearing_price: Uint128::new(6_000_000),
+            bought_now: false,
+        }
+    );
+    let err = contract
+        .bid(&mut app, &sender1, &coins(5_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::ClosedAcution);
+
+    // The price does not fall below the floor
+    app.update_block(|block| block.time = block.time.plus_seconds(1_000));
+    assert_eq!(
+        contract.query_current_price(&app).unwrap(),
+        Uint128::new(2_000_000)
+    );
+}
+
+#[test]
+fn dutch_auction_stepwise() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract with a price falling by 1 atom every 100s, from
+    // a start 50s ahead
+    let start = app.block_info().time.plus_seconds(50);
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            start: Some(Scheduled::AtTime(start)),
+            kind: Some(AuctionKind::Dutch(DutchAuction {
+                start_price: Uint128::new(5_000_000),
+                floor_price: Uint128::new(2_500_000),
+                decline: Decline::Stepwise {
+                    interval: 100,
+                    amount: Uint128::new(1_000_000),
+                },
+            })),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // The price starts falling at the auction start
+    app.update_block(|block| block.time = block.time.plus_seconds(149));
+    assert_eq!(
+        contract.query_current_price(&app).unwrap(),
+        Uint128::new(5_000_000)
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(1));
+    assert_eq!(
+        contract.query_current_price(&app).unwrap(),
+        Uint128::new(4_000_000)
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+    assert_eq!(
+        contract.query_current_price(&app).unwrap(),
+        Uint128::new(2_500_000)
+    );
+}
+
+#[test]
+fn invalid_dutch_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    let dutch = DutchAuction {
+        start_price: Uint128::new(5_000_000),
+        floor_price: Uint128::new(1_000_000),
+        decline: Decline::Linear { duration: 1_000 },
+    };
+
+    // The price must fall from above the floor
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            kind: Some(AuctionKind::Dutch(DutchAuction {
+                floor_price: Uint128::new(6_000_000),
+                ..dutch.clone()
+            })),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidDutchAuction);
+
+    // Rules on ascending bids are not supported
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            kind: Some(AuctionKind::Dutch(dutch)),
+            reserve_price: Some(Uint128::new(2_000_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidDutchAuction);
+
+    // An English auction has no current price
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &base_msg(&owner),
+    )
+    .unwrap();
+    contract.query_current_price(&app).unwrap_err();
+}
+// END --> Dutch Auction Tests
+
+// START --> Sealed Auction Tests
+#[test]
+fn sealed_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    let sender3 = Addr::unchecked("sender3");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2, &sender3] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract committing bids for 500s and revealing them for
+    // other 500s
+    let now = app.block_info().time;
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Flat(Uint128::new(100_000))),
+            end: Some(Scheduled::AtTime(now.plus_seconds(1_000))),
+            kind: Some(AuctionKind::Sealed(SealedAuction {
+                commit_end: Scheduled::AtTime(now.plus_seconds(500)),
+                unrevealed: UnrevealedDeposits::Refund,
+            })),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // Bids are only committed, with a deposit covering them
+    let err = contract
+        .bid(&mut app, &sender1, &coins(4_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPhase);
+
+    let hash1 = commitment_hash(&sender1, Uint128::new(4_000_000), b"salt1");
+    contract
+        .commit(&mut app, &sender1, &hash1, &coins(5_000_000, UATOM))
+        .unwrap();
+    let hash2 = commitment_hash(&sender2, Uint128::new(6_000_000), b"salt2");
+    contract
+        .commit(&mut app, &sender2, &hash2, &coins(6_100_000, UATOM))
+        .unwrap();
+    let hash3 = commitment_hash(&sender3, Uint128::new(8_000_000), b"salt3");
+    contract
+        .commit(&mut app, &sender3, &hash3, &coins(9_000_000, UATOM))
+        .unwrap();
+
+    let err = contract
+        .commit(&mut app, &sender1, &hash1, &coins(5_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyCommitted);
+    assert_eq!(
+        contract.query_commitment(&app, &sender1).unwrap(),
+        Commitment {
+            hash: hash1.clone(),
+            deposit: Uint128::new(5_000_000)
+        }
+    );
+
+    // Bids cannot be revealed before the commit end
+    let err = contract
+        .reveal(&mut app, &sender1, 4_000_000, b"salt1")
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPhase);
+    contract.query_highest_bid(&app).unwrap_err();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(500));
+
+    // Bids cannot be committed after the commit end
+    let err = contract
+        .commit(&mut app, &sender1, &hash1, &coins(5_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPhase);
+
+    // The revealed bid must match the commitment
+    let err = contract
+        .reveal(&mut app, &sender1, 4_000_000, b"salt2")
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidReveal);
+
+    // Revealing refunds the deposit exceeding the bid and its commission
+    contract
+        .reveal(&mut app, &sender1, 4_000_000, b"salt1")
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(5_900_000, UATOM)
+    );
+    contract
+        .reveal(&mut app, &sender2, 6_000_000, b"salt2")
+        .unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        BidResp {
+            address: sender2.clone(),
+            amount: Uint128::new(6_000_000)
+        }
+    );
+
+    // The auction cannot be closed before the end of the reveals
+    let err = contract.close(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::OpenAcution);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(500));
+
+    // The highest revealed bid wins, the bid never revealed does not count
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        contract.query_winner(&app).unwrap(),
+        WinnerResp::Won {
+            address: sender2.clone(),
+            amount: Uint128::new(6_000_000),
+            clearing_price: Uint128::new(6_000_000),
+            bought_now: false,
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(6_200_000, UATOM)
+    );
+
+    // Losing bids and unrevealed deposits are retracted
+    contract.retract(&mut app, &sender1, None).unwrap();
+    contract.retract(&mut app, &sender3, None).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(9_900_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&sender3).unwrap(),
+        coins(10_000_000, UATOM)
+    );
+    let err = contract.retract(&mut app, &sender2, None).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRetract);
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        vec![]
+    );
+}
+
+#[test]
+fn sealed_auction_forfeit() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract forfeiting the deposits never revealed
+    let now = app.block_info().time;
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            end: Some(Scheduled::AtTime(now.plus_seconds(1_000))),
+            kind: Some(AuctionKind::Sealed(SealedAuction {
+                commit_end: Scheduled::AtTime(now.plus_seconds(500)),
+                unrevealed: UnrevealedDeposits::Forfeit,
+            })),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    let hash1 = commitment_hash(&sender1, Uint128::new(4_000_000), b"salt1");
+    contract
+        .commit(&mut app, &sender1, &hash1, &coins(5_000_000, UATOM))
+        .unwrap();
+    let hash2 = commitment_hash(&sender2, Uint128::new(6_000_000), b"salt2");
+    contract
+        .commit(&mut app, &sender2, &hash2, &coins(3_000_000, UATOM))
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(500));
+
+    // The deposit must cover the revealed bid
+    let err = contract
+        .reveal(&mut app, &sender2, 6_000_000, b"salt2")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientDeposit {
+            deposit: Uint128::new(3_000_000),
+            amount: Uint128::new(6_000_000),
+            commission: Uint128::zero()
+        }
+    );
+    contract
+        .reveal(&mut app, &sender1, 4_000_000, b"salt1")
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(500));
+
+    // Settling sends the unrevealed deposits along with the winning bid
+    contract.settle(&mut app, &sender1).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(7_000_000, UATOM)
+    );
+    let err = contract.retract(&mut app, &sender2, None).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRetract);
+}
+
+#[test]
+fn invalid_sealed_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Bids must be committed before the auction end
+    let now = app.block_info().time;
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            end: Some(Scheduled::AtTime(now.plus_seconds(500))),
+            kind: Some(AuctionKind::Sealed(SealedAuction {
+                commit_end: Scheduled::AtTime(now.plus_seconds(500)),
+                unrevealed: UnrevealedDeposits::Refund,
+            })),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSealedAuction);
+
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            kind: Some(AuctionKind::Sealed(SealedAuction {
+                commit_end: Scheduled::AtTime(now.plus_seconds(500)),
+                unrevealed: UnrevealedDeposits::Refund,
+            })),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSealedAuction);
+
+    // The minimum of sealed bids is the reserve price, not a starting price
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            end: Some(Scheduled::AtTime(now.plus_seconds(1_000))),
+            starting_price: Some(Uint128::new(5_000_000)),
+            kind: Some(AuctionKind::Sealed(SealedAuction {
+                commit_end: Scheduled::AtTime(now.plus_seconds(500)),
+                unrevealed: UnrevealedDeposits::Refund,
+            })),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSealedAuction);
+}
+// END --> Sealed Auction Tests
+
+// START --> Second Price Tests
+#[test]
+fn second_price_open_bids() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract where the winner pays the runner-up bid
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            pricing: Some(Pricing::SecondPrice),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender1, &coins(4_000_000, UATOM))
+        .unwrap();
+    contract
+        .bid(&mut app, &sender2, &coins(7_000_000, UATOM))
+        .unwrap();
+    contract
+        .bid(&mut app, &sender1, &coins(5_000_000, UATOM))
+        .unwrap();
+
+    // The winner pays the second highest total bid
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(
+        contract.query_winner(&app).unwrap(),
+        WinnerResp::Won {
+            address: sender1.clone(),
+            amount: Uint128::new(9_000_000),
+            clearing_price: Uint128::new(7_000_000),
+            bought_now: false,
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(7_000_000, UATOM)
+    );
+
+    // The difference is refundable to the winner
+    assert_eq!(
+        contract.query_total_bid(&app, &sender1).unwrap(),
+        Uint128::new(2_000_000)
+    );
+    contract.retract(&mut app, &sender1, None).unwrap();
+    contract.retract(&mut app, &sender2, None).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(3_000_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&sender2).unwrap(),
+        coins(10_000_000, UATOM)
+    );
+}
+
+#[test]
+fn second_price_sealed_bids() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    let sender3 = Addr::unchecked("sender3");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2, &sender3] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate a sealed bid auction where the winner pays the runner-up
+    // bid, but not less than the reserve price
+    let now = app.block_info().time;
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            end: Some(Scheduled::AtTime(now.plus_seconds(1_000))),
+            reserve_price: Some(Uint128::new(6_000_000)),
+            kind: Some(AuctionKind::Sealed(SealedAuction {
+                commit_end: Scheduled::AtTime(now.plus_seconds(500)),
+                unrevealed: UnrevealedDeposits::Refund,
+            })),
+            pricing: Some(Pricing::SecondPrice),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    let bids = [
+        (&sender1, 8_000_000, b"salt1"),
+        (&sender2, 5_000_000, b"salt2"),
+        (&sender3, 3_000_000, b"salt3"),
+    ];
+    for (sender, amount, salt) in bids {
+        let hash = commitment_hash(sender, Uint128::new(amount), salt);
+        contract
+            .commit(&mut app, sender, &hash, &coins(amount, UATOM))
+            .unwrap();
+    }
+
+    app.update_block(|block| block.time = block.time.plus_seconds(500));
+    for (sender, amount, salt) in bids {
+        contract.reveal(&mut app, sender, amount, salt).unwrap();
+    }
+
+    app.update_block(|block| block.time = block.time.plus_seconds(500));
+
+    // The runner-up bid is below the reserve price, which is paid instead
+    contract.settle(&mut app, &owner).unwrap();
+    assert_eq!(
+        contract.query_winner(&app).unwrap(),
+        WinnerResp::Won {
+            address: sender1.clone(),
+            amount: Uint128::new(8_000_000),
+            clearing_price: Uint128::new(6_000_000),
+            bought_now: false,
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(6_000_000, UATOM)
+    );
+    contract.retract(&mut app, &sender1, None).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(4_000_000, UATOM)
+    );
+}
+// END --> Second Price Tests
+
+// START --> Multi-Unit Tests
+#[test]
+fn multi_unit_uniform_price() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    let sender3 = Addr::unchecked("sender3");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2, &sender3] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract selling 5 identical units
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            quantity: Some(5),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid_units(&mut app, &sender1, 2_000_000, 3, &coins(6_000_000, UATOM))
+        .unwrap();
+    contract
+        .bid_units(&mut app, &sender2, 3_000_000, 2, &coins(6_000_000, UATOM))
+        .unwrap();
+    contract
+        .bid_units(&mut app, &sender3, 1_000_000, 2, &coins(2_000_000, UATOM))
+        .unwrap();
+
+    // The lowest bid is not allocated any unit
+    assert_eq!(
+        contract.query_allocations(&app).unwrap(),
+        AllocationsResp {
+            clearing_price: Some(Uint128::new(2_000_000)),
+            allocations: vec![(sender2.clone(), 2), (sender1.clone(), 3)],
+        }
+    );
+
+    // Raising the bid only requires the missing deposit
+    contract
+        .bid_units(&mut app, &sender3, 2_500_000, 2, &coins(3_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_total_bid(&app, &sender3).unwrap(),
+        Uint128::new(5_000_000)
+    );
+
+    // The last winning bid is partially filled and sets the clearing price
+    let allocations = AllocationsResp {
+        clearing_price: Some(Uint128::new(2_000_000)),
+        allocations: vec![
+            (sender2.clone(), 2),
+            (sender3.clone(), 2),
+            (sender1.clone(), 1),
+        ],
+    };
+    assert_eq!(contract.query_allocations(&app).unwrap(), allocations);
+
+    // Each unit is paid at the clearing price
+    contract.close(&mut app, &owner).unwrap();
+    assert_eq!(contract.query_allocations(&app).unwrap(), allocations);
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(10_000_000, UATOM)
+    );
+
+    // The rest of the deposits is refundable
+    for sender in [&sender1, &sender2, &sender3] {
+        contract.retract(&mut app, sender, None).unwrap();
+    }
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(8_000_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&sender2).unwrap(),
+        coins(6_000_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&sender3).unwrap(),
+        coins(6_000_000, UATOM)
+    );
+
+    let err = contract.query_winner(&app).unwrap_err();
+    assert!(err.to_string().contains("allocations rather than a winner"));
+}
+
+#[test]
+fn multi_unit_invalid_bids() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // The supply must be sold without an on-chain lot
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            quantity: Some(0),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidMultiUnitAuction);
+
+    // Instantiate contract selling 3 units with a reserve price per unit and
+    // a commission on each bid
+    let now = app.block_info().time;
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Flat(Uint128::new(100_000))),
+            end: Some(Scheduled::AtTime(now.plus_seconds(1_000))),
+            reserve_price: Some(Uint128::new(1_000_000)),
+            quantity: Some(3),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // Bids must carry a price and a quantity within the supply
+    let err = contract
+        .bid(&mut app, &sender1, &coins(1_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidUnitBid);
+    let err = contract
+        .bid_units(&mut app, &sender1, 1_000_000, 4, &coins(4_100_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidUnitBid);
+
+    // The deposit, without commission, must cover the bid
+    let err = contract
+        .bid_units(&mut app, &sender1, 1_000_000, 2, &coins(2_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientUnitDeposit {
+            deposit: Uint128::new(1_900_000),
+            price: Uint128::new(1_000_000),
+            quantity: 2,
+        }
+    );
+    contract
+        .bid_units(&mut app, &sender1, 1_000_000, 2, &coins(2_100_000, UATOM))
+        .unwrap();
+
+    // A new bid cannot lower the previous one
+    let err = contract
+        .bid_units(&mut app, &sender1, 900_000, 2, &coins(100_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidUnitBid);
+
+    // Bids below the reserve price are not allocated any unit
+    contract
+        .bid_units(&mut app, &sender2, 500_000, 1, &coins(600_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_allocations(&app).unwrap(),
+        AllocationsResp {
+            clearing_price: Some(Uint128::new(1_000_000)),
+            allocations: vec![(sender1.clone(), 2)],
+        }
+    );
+
+    // Once the auction has ended, winning deposits cannot be retracted until
+    // the auction is settled
+    app.update_block(|block| block.time = block.time.plus_seconds(1_000));
+    let err = contract.retract(&mut app, &sender1, None).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRetract);
+    contract.retract(&mut app, &sender2, None).unwrap();
+
+    contract.settle(&mut app, &owner).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(2_200_000, UATOM)
+    );
+    let err = contract.retract(&mut app, &sender1, None).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRetract);
+}
+// END --> Multi-Unit Tests
+
+// START --> Buy Now Tests
+#[test]
+fn buy_now_closes_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(20_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // The buy-it-now price cannot be below the reserve price
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            reserve_price: Some(Uint128::new(12_000_000)),
+            buy_now_price: Some(Uint128::new(10_000_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidBuyNowPrice);
+
+    // Instantiate contract with a buy-it-now price
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            min_increment: Some(Increment::Absolute(Uint128::new(1_000_000))),
+            buy_now_price: Some(Uint128::new(10_000_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender1, &coins(4_000_000, UATOM))
+        .unwrap();
+
+    // Buying requires the whole buy-it-now price
+    let err = contract
+        .buy_now(&mut app, &sender2, &coins(9_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BelowBuyNowPrice {
+            new_bid: Uint128::new(9_000_000),
+            price: Uint128::new(10_000_000),
+        }
+    );
+
+    // The lot is bought at the buy-it-now price, the excess is refunded
+    contract
+        .buy_now(&mut app, &sender2, &coins(12_000_000, UATOM))
+        .unwrap();
+    assert!(contract.query_is_closed(&app).unwrap());
+    assert_eq!(
+        contract.query_winner(&app).unwrap(),
+        WinnerResp::Won {
+            address: sender2.clone(),
+            amount: Uint128::new(10_000_000),
+            clearing_price: Uint128::new(10_000_000),
+            bought_now: true,
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(10_000_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&sender2).unwrap(),
+        coins(10_000_000, UATOM)
+    );
+
+    // No further bid is accepted and the other bids are refundable
+    let err = contract
+        .bid(&mut app, &sender1, &coins(8_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::ClosedAcution);
+    contract.retract(&mut app, &sender1, None).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(20_000_000, UATOM)
+    );
+}
+
+#[test]
+fn bid_reaching_buy_now_price() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(20_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract with a commission and a second-price settlement
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Flat(Uint128::new(500_000))),
+            pricing: Some(Pricing::SecondPrice),
+            buy_now_price: Some(Uint128::new(10_000_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender1, &coins(4_500_000, UATOM))
+        .unwrap();
+    contract
+        .bid(&mut app, &sender2, &coins(5_500_000, UATOM))
+        .unwrap();
+
+    // Raising the total bid, without commission, to the buy-it-now price
+    // closes the auction, which is paid the buy-it-now price as it is
+    contract
+        .bid(&mut app, &sender1, &coins(6_500_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_winner(&app).unwrap(),
+        WinnerResp::Won {
+            address: sender1.clone(),
+            amount: Uint128::new(10_000_000),
+            clearing_price: Uint128::new(10_000_000),
+            bought_now: true,
+        }
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(11_500_000, UATOM)
+    );
+
+    // Auctions without a buy-it-now price cannot be bought right away
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &base_msg(&owner),
+    )
+    .unwrap();
+    let err = contract
+        .buy_now(&mut app, &sender1, &coins(1_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::NoBuyNowPrice);
+}
+
+#[test]
+fn buy_now_commission_on_price() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+    let buyer = Addr::unchecked("buyer");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(5_000_000, UATOM))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &buyer, coins(1_100_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract with a 10% commission and a buy-it-now price
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Percentage { bps: 1_000 }),
+            buy_now_price: Some(Uint128::new(1_000_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // The commission is charged on the buy-it-now price, not on the refunded
+    // excess
+    contract
+        .buy_now(&mut app, &sender, &coins(5_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender).unwrap(),
+        coins(3_900_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(1_100_000, UATOM)
+    );
+    assert_eq!(app.wrap().query_all_balances(contract.addr()).unwrap(), &[]);
+
+    // Sending exactly the buy-it-now price and its commission is enough
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Percentage { bps: 1_000 }),
+            buy_now_price: Some(Uint128::new(1_000_000)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+    contract
+        .buy_now(&mut app, &buyer, &coins(1_100_000, UATOM))
+        .unwrap();
+    assert_eq!(app.wrap().query_all_balances(&buyer).unwrap(), &[]);
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(2_200_000, UATOM)
+    );
+}
+// END --> Buy Now Tests
+
+// START --> Cancel Tests
+#[test]
+fn cancel_refunds_every_bid() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract with a commission kept by the owner on cancel
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Flat(Uint128::new(1_000_000))),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender1, &coins(5_000_000, UATOM))
+        .unwrap();
+    contract
+        .bid(&mut app, &sender2, &coins(7_000_000, UATOM))
+        .unwrap();
+
+    // Only the owner can cancel the auction
+    let err = contract
+        .cancel(&mut app, &sender1, "Lot damaged", &[])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+    contract
+        .cancel(&mut app, &owner, "Lot damaged", &[])
+        .unwrap();
+
+    // The auction is over without a winner
+    assert!(contract.query_is_closed(&app).unwrap());
+    assert_eq!(
+        contract
+            .query_list_auctions(&app, None, None, None)
+            .unwrap()
+            .auctions[0]
+            .status,
+        Status::Cancelled
+    );
+    let err = contract.query_winner(&app).unwrap_err();
+    assert!(err.to_string().contains("cancelled"));
+    let err = contract.close(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::ClosedAcution);
+    let err = contract
+        .bid(&mut app, &sender1, &coins(3_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::ClosedAcution);
+
+    // Every bid is refundable, including the highest one
+    contract.retract(&mut app, &sender2, None).unwrap();
+    contract.retract(&mut app, &sender1, None).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&sender1).unwrap(),
+        coins(9_000_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&sender2).unwrap(),
+        coins(9_000_000, UATOM)
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(2_000_000, UATOM)
+    );
+}
+
+#[test]
+fn cancel_refunds_commissions() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate contract refunding commissions on cancel, part of them is
+    // set aside for the keeper reward
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            commission: Some(CommissionPolicy::Flat(Uint128::new(1_000_000))),
+            keeper_reward: Some(Uint128::new(500_000)),
+            refund_commissions: Some(true),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender1, &coins(5_000_000, UATOM))
+        .unwrap();
+    contract
+        .bid(&mut app, &sender2, &coins(7_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(1_500_000, UATOM)
+    );
+
+    // The owner pays back the commissions already received
+    let err = contract
+        .cancel(&mut app, &owner, "Lot damaged", &coins(1_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidCancelFunds {
+            required: Uint128::new(1_500_000),
+            funds: Uint128::new(1_000_000),
+        }
+    );
+    contract
+        .cancel(&mut app, &owner, "Lot damaged", &coins(1_500_000, UATOM))
+        .unwrap();
+
+    // Bidders retract their whole funds, commissions included
+    contract.retract(&mut app, &sender1, None).unwrap();
+    contract.retract(&mut app, &sender2, None).unwrap();
+    for sender in [&sender1, &sender2] {
+        assert_eq!(
+            app.wrap().query_all_balances(sender).unwrap(),
+            coins(10_000_000, UATOM)
+        );
+    }
+    assert_eq!(app.wrap().query_all_balances(&owner).unwrap(), vec![]);
+    assert_eq!(
+        app.wrap().query_all_balances(contract.addr()).unwrap(),
+        vec![]
+    );
+}
+#[test]
+fn cancel_after_end() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender = Addr::unchecked("sender");
+    let keeper = Addr::unchecked("keeper");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(5_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+    let end = app.block_info().time.plus_seconds(100);
+
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            end: Some(Scheduled::AtTime(end)),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender, &coins(5_000_000, UATOM))
+        .unwrap();
+
+    // Once the end has passed the auction has a winner, so it cannot be
+    // cancelled before being settled
+    app.update_block(|block| block.time = end);
+    let err = contract
+        .cancel(&mut app, &owner, "Changed my mind", &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::ClosedAcution);
+
+    contract.settle(&mut app, &keeper).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(5_000_000, UATOM)
+    );
+}
+// END --> Cancel Tests
+
+// START --> Ownership Tests
+#[test]
+fn transfer_ownership() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let new_owner = Addr::unchecked("new_owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&new_owner, &sender1, &sender2] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &base_msg(&owner),
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender1, &coins(3_000_000, UATOM))
+        .unwrap();
+
+    // A bidder cannot become the owner while the auction is open
+    let err = contract
+        .transfer_ownership(&mut app, &owner, &sender1, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OwnerWithBids {
+            owner: sender1.to_string()
+        }
+    );
+
+    // Only the owner can transfer the ownership
+    let err = contract
+        .transfer_ownership(&mut app, &sender2, &sender2, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    let expiry = Expiration::AtTime(app.block_info().time.plus_seconds(100));
+    contract
+        .transfer_ownership(&mut app, &owner, &new_owner, expiry)
+        .unwrap();
+    assert_eq!(
+        contract.query_ownership(&app).unwrap(),
+        OwnershipResp {
+            owner: Some(owner.clone()),
+            pending_owner: Some(new_owner.clone()),
+            pending_expiry: Some(expiry),
+        }
+    );
+
+    // Only the pending owner can accept the ownership
+    let err = contract.accept_ownership(&mut app, &sender2).unwrap_err();
+    assert_eq!(err, ContractError::NoPendingOwnership);
+    contract.accept_ownership(&mut app, &new_owner).unwrap();
+    assert_eq!(
+        contract.query_ownership(&app).unwrap(),
+        OwnershipResp {
+            owner: Some(new_owner.clone()),
+            pending_owner: None,
+            pending_expiry: None,
+        }
+    );
+
+    // The new owner manages the auction and cannot bid anymore
+    let err = contract.close(&mut app, &owner).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: new_owner.to_string()
+        }
+    );
+    let err = contract
+        .bid(&mut app, &new_owner, &coins(5_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidBid {
+            owner: new_owner.to_string()
+        }
+    );
+
+    // The winning bid goes to the new owner
+    contract.close(&mut app, &new_owner).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&new_owner).unwrap(),
+        coins(13_000_000, UATOM)
+    );
+}
+
+#[test]
+fn expired_transfer_and_renounce_ownership() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let new_owner = Addr::unchecked("new_owner");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, coins(10_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // An auction without end cannot be renounced
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &base_msg(&owner),
+    )
+    .unwrap();
+    let err = contract.renounce_ownership(&mut app, &owner).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRenounce);
+
+    let now = app.block_info().time;
+    let contract = contract
+        .create_auction(
+            &mut app,
+            &owner,
+            &InstantiateMsg {
+                end: Some(Scheduled::AtTime(now.plus_seconds(1_000))),
+                ..base_msg(&owner)
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The ownership cannot be accepted once the transfer has expired
+    contract
+        .transfer_ownership(
+            &mut app,
+            &owner,
+            &new_owner,
+            Expiration::AtTime(now.plus_seconds(100)),
+        )
+        .unwrap();
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    let err = contract.accept_ownership(&mut app, &new_owner).unwrap_err();
+    assert_eq!(err, ContractError::OwnershipExpired);
+
+    // Nobody can manage the auction after it has been renounced
+    contract.renounce_ownership(&mut app, &owner).unwrap();
+    assert_eq!(
+        contract.query_ownership(&app).unwrap(),
+        OwnershipResp {
+            owner: None,
+            pending_owner: None,
+            pending_expiry: None,
+        }
+    );
+    let err = contract.close(&mut app, &owner).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    // The auction is settled once ended, paying the former owner
+    contract
+        .bid(&mut app, &sender, &coins(4_000_000, UATOM))
+        .unwrap();
+    app.update_block(|block| block.time = block.time.plus_seconds(900));
+    contract.settle(&mut app, &sender).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(4_000_000, UATOM)
+    );
+}
+// END --> Ownership Tests
+
+// START --> Operator Tests
+#[test]
+fn operator_closes_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let operator = Addr::unchecked("operator");
+    let sender = Addr::unchecked("sender");
+
+    let mut app = App::new(|router, _api, storage| {
+        for address in [&operator, &sender] {
+            router
+                .bank
+                .init_balance(storage, address, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &base_msg(&owner),
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender, &coins(4_000_000, UATOM))
+        .unwrap();
+
+    // A bidder cannot become an operator while the auction is open
+    let err = contract
+        .add_operator(&mut app, &owner, &sender, &[Permission::Close])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OperatorWithBids {
+            operator: sender.to_string()
+        }
+    );
+
+    contract
+        .add_operator(&mut app, &owner, &operator, &[Permission::Close])
+        .unwrap();
+
+    // Operators cannot bid
+    let err = contract
+        .bid(&mut app, &operator, &coins(5_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OperatorBid {
+            operator: operator.to_string()
+        }
+    );
+
+    // The operator can only perform the actions it has been granted
+    let err = contract
+        .cancel(&mut app, &operator, "Lot damaged", &[])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+
+    // The auction closed by the operator pays the owner
+    contract.close(&mut app, &operator).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(4_000_000, UATOM)
+    );
+}
+
+#[test]
+fn operators_cleared_on_ownership_transfer() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let new_owner = Addr::unchecked("new_owner");
+    let operator = Addr::unchecked("operator");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &base_msg(&owner),
+    )
+    .unwrap();
+
+    contract
+        .add_operator(&mut app, &owner, &operator, &[Permission::Close])
+        .unwrap();
+    contract
+        .transfer_ownership(&mut app, &owner, &new_owner, None)
+        .unwrap();
+    contract.accept_ownership(&mut app, &new_owner).unwrap();
+
+    // The operator of the previous owner cannot close the auction anymore
+    let err = contract.close(&mut app, &operator).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: new_owner.to_string()
+        }
+    );
+}
+
+#[test]
+fn manage_operators() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let operator = Addr::unchecked("operator");
+
+    let mut app = App::default();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &base_msg(&owner),
+    )
+    .unwrap();
+
+    // Only the owner manages the operators, with at least one permission
+    let err = contract
+        .add_operator(&mut app, &operator, &operator, &[Permission::Close])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+    let err = contract
+        .add_operator(&mut app, &owner, &operator, &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::EmptyPermissions);
+
+    let permissions = [Permission::UpdateMetadata, Permission::Cancel];
+    contract
+        .add_operator(&mut app, &owner, &operator, &permissions)
+        .unwrap();
+    assert_eq!(
+        contract.query_operators(&app).unwrap(),
+        OperatorsResp {
+            operators: vec![(operator.clone(), permissions.to_vec())],
+        }
+    );
+
+    // The operator updates the description of the auction
+    contract
+        .update_metadata(&mut app, &operator, "Supercomputer #2208 bidding")
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_list_auctions(&app, None, None, None)
+            .unwrap()
+            .auctions[0]
+            .description,
+        "Supercomputer #2208 bidding"
+    );
+
+    // A removed operator cannot act anymore
+    contract
+        .remove_operator(&mut app, &owner, &operator)
+        .unwrap();
+    assert_eq!(
+        contract.query_operators(&app).unwrap(),
+        OperatorsResp { operators: vec![] }
+    );
+    let err = contract
+        .update_metadata(&mut app, &operator, "Supercomputer #2209 bidding")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+    let err = contract
+        .remove_operator(&mut app, &owner, &operator)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotOperator {
+            address: operator.to_string()
+        }
+    );
+
+    // An operator granted to cancel the auction can do it
+    contract
+        .add_operator(&mut app, &owner, &operator, &[Permission::Cancel])
+        .unwrap();
+    contract
+        .cancel(&mut app, &operator, "Lot damaged", &[])
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_list_auctions(&app, None, None, None)
+            .unwrap()
+            .auctions[0]
+            .status,
+        Status::Cancelled
+    );
+}
+// END --> Operator Tests
+
+// START --> Access List Tests
+#[test]
+fn allowlist_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let operator = Addr::unchecked("operator");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate a private auction, for the members of the access list only
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            access: Some(AccessControl::Allowlist),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    let err = contract
+        .bid(&mut app, &sender1, &coins(1_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BidderNotAllowed {
+            bidder: sender1.to_string()
+        }
+    );
+
+    // Only the owner, or an operator allowed to, manages the access list
+    let err = contract
+        .update_access_list(&mut app, &sender1, &[&sender1], &[])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+    contract
+        .add_operator(&mut app, &owner, &operator, &[Permission::Close])
+        .unwrap();
+    let err = contract
+        .update_access_list(&mut app, &operator, &[&sender1], &[])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+    contract
+        .add_operator(&mut app, &owner, &operator, &[Permission::ManageAccess])
+        .unwrap();
+    contract
+        .update_access_list(&mut app, &operator, &[&sender1], &[])
+        .unwrap();
+    contract
+        .update_access_list(&mut app, &owner, &[&sender2], &[])
+        .unwrap();
+
+    contract
+        .bid(&mut app, &sender1, &coins(1_000_000, UATOM))
+        .unwrap();
+
+    // Members are listed page by page
+    assert_eq!(
+        contract.query_access_list(&app, None, 1).unwrap(),
+        AccessListResp {
+            access: AccessControl::Allowlist,
+            members: vec![sender1.clone()],
+        }
+    );
+    assert_eq!(
+        contract.query_access_list(&app, &sender1, None).unwrap(),
+        AccessListResp {
+            access: AccessControl::Allowlist,
+            members: vec![sender2.clone()],
+        }
+    );
+
+    // Removed members cannot bid anymore
+    contract
+        .update_access_list(&mut app, &owner, &[], &[&sender2])
+        .unwrap();
+    let err = contract
+        .bid(&mut app, &sender2, &coins(2_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BidderNotAllowed {
+            bidder: sender2.to_string()
+        }
+    );
+}
+
+#[test]
+fn denylist_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Instantiate an auction open to anyone but the members of the access list
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            access: Some(AccessControl::Denylist),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    contract
+        .update_access_list(&mut app, &owner, &[&sender1], &[])
+        .unwrap();
+
+    let err = contract
+        .bid(&mut app, &sender1, &coins(1_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BidderNotAllowed {
+            bidder: sender1.to_string()
+        }
+    );
+    contract
+        .bid(&mut app, &sender2, &coins(1_000_000, UATOM))
+        .unwrap();
+}
+// END --> Access List Tests
+
+// START --> Merkle Tests
+fn merkle_leaf(address: &Addr) -> Binary {
+    Binary::from(Sha256::digest(address.as_bytes()).as_slice())
+}
+
+fn merkle_node(a: &Binary, b: &Binary) -> Binary {
+    let (left, right) = if a.as_slice() <= b.as_slice() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let hash = Sha256::new()
+        .chain_update(left.as_slice())
+        .chain_update(right.as_slice())
+        .finalize();
+    Binary::from(hash.as_slice())
+}
+
+#[test]
+fn merkle_gated_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let operator = Addr::unchecked("operator");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    let sender3 = Addr::unchecked("sender3");
+    let outsider = Addr::unchecked("outsider");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2, &sender3, &outsider] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    // Tree of the eligible bidders: ((sender1, sender2), sender3)
+    let leaf1 = merkle_leaf(&sender1);
+    let leaf2 = merkle_leaf(&sender2);
+    let leaf3 = merkle_leaf(&sender3);
+    let node12 = merkle_node(&leaf1, &leaf2);
+    let root = merkle_node(&node12, &leaf3);
+
+    // A Merkle root must be a sha256 hash
+    let err = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            merkle_root: Some(Binary::from(b"root".as_slice())),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidMerkleRoot);
+
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            merkle_root: Some(root.clone()),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // The first bid needs a valid proof
+    let err = contract
+        .bid(&mut app, &sender1, &coins(1_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotEligible {
+            bidder: sender1.to_string()
+        }
+    );
+    let err = contract
+        .bid_with_proof(
+            &mut app,
+            &outsider,
+            &coins(1_000_000, UATOM),
+            &[leaf2.clone(), leaf3.clone()],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotEligible {
+            bidder: outsider.to_string()
+        }
+    );
+
+    contract
+        .bid_with_proof(
+            &mut app,
+            &sender1,
+            &coins(1_000_000, UATOM),
+            &[leaf2.clone(), leaf3],
+        )
+        .unwrap();
+    contract
+        .bid_with_proof(&mut app, &sender3, &coins(2_000_000, UATOM), &[node12])
+        .unwrap();
+
+    // Later bids do not need the proof again
+    contract
+        .bid(&mut app, &sender1, &coins(2_000_000, UATOM))
+        .unwrap();
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        BidResp {
+            address: sender1.clone(),
+            amount: Uint128::new(3_000_000)
+        }
+    );
+
+    // Only the owner, or an operator allowed to manage the access, can change
+    // the root
+    let err = contract
+        .set_merkle_root(&mut app, &sender1, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            owner: owner.to_string()
+        }
+    );
+    contract
+        .add_operator(&mut app, &owner, &operator, &[Permission::ManageAccess])
+        .unwrap();
+
+    // Bidders verified against a previous root have to prove it again
+    let root = merkle_node(&leaf2, &merkle_leaf(&sender1));
+    contract.set_merkle_root(&mut app, &operator, root).unwrap();
+    let err = contract
+        .bid(&mut app, &sender1, &coins(2_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotEligible {
+            bidder: sender1.to_string()
+        }
+    );
+    contract
+        .bid_with_proof(&mut app, &sender1, &coins(2_000_000, UATOM), &[leaf2])
+        .unwrap();
+
+    // Removing the root lets anyone bid
+    contract.set_merkle_root(&mut app, &owner, None).unwrap();
+    contract
+        .bid(&mut app, &outsider, &coins(6_000_000, UATOM))
+        .unwrap();
+}
+
+#[test]
+fn merkle_gated_buy_now() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender1, coins(2_000_000, UATOM))
+            .unwrap();
+    });
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    let leaf1 = merkle_leaf(&sender1);
+    let leaf2 = merkle_leaf(&sender2);
+    let root = merkle_node(&leaf1, &leaf2);
+
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            buy_now_price: Some(Uint128::new(2_000_000)),
+            merkle_root: Some(root),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // Buying the lot without an earlier bid needs a proof as well
+    let err = contract
+        .buy_now(&mut app, &sender1, &coins(2_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotEligible {
+            bidder: sender1.to_string()
+        }
+    );
+    contract
+        .buy_now_with_proof(&mut app, &sender1, &coins(2_000_000, UATOM), &[leaf2])
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(&owner).unwrap(),
+        coins(2_000_000, UATOM)
+    );
+}
+// END --> Merkle Tests
+
+// START --> Group Tests
+// Store the cw4-group code and instantiate a group with the given members
+fn instantiate_cw4_group(app: &mut App, admin: &Addr, members: &[(&Addr, u64)]) -> Addr {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    let code_id = app.store_code(Box::new(contract));
+
+    app.instantiate_contract(
+        code_id,
+        admin.clone(),
+        &Cw4GroupInstantiateMsg {
+            admin: Some(admin.to_string()),
+            members: members
+                .iter()
+                .map(|(addr, weight)| Member {
+                    addr: addr.to_string(),
+                    weight: *weight,
+                })
+                .collect(),
+        },
+        &[],
+        "Approved buyers",
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn group_gated_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    let outsider = Addr::unchecked("outsider");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&sender1, &sender2, &outsider] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let group = instantiate_cw4_group(&mut app, &owner, &[(&sender1, 10), (&sender2, 1)]);
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            group: Some(UncheckedGroupGate {
+                group: group.to_string(),
+                min_weight: 5,
+            }),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    // Non-members and members below the minimum weight cannot bid
+    for sender in [&outsider, &sender2] {
+        let err = contract
+            .bid(&mut app, sender, &coins(1_000_000, UATOM))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotGroupMember {
+                bidder: sender.to_string(),
+                min_weight: 5
+            }
+        );
+    }
+
+    contract
+        .bid(&mut app, &sender1, &coins(1_000_000, UATOM))
+        .unwrap();
+
+    // Membership is checked against the group at the time of the bid
+    app.execute_contract(
+        owner.clone(),
+        group,
+        &Cw4GroupExecuteMsg::UpdateMembers {
+            remove: vec![sender1.to_string()],
+            add: vec![Member {
+                addr: sender2.to_string(),
+                weight: 5,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+
+    contract
+        .bid(&mut app, &sender2, &coins(2_000_000, UATOM))
+        .unwrap();
+    let err = contract
+        .bid(&mut app, &sender1, &coins(2_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotGroupMember {
+            bidder: sender1.to_string(),
+            min_weight: 5
+        }
+    );
+}
+// END --> Group Tests
+
+// START --> NFT Gate Tests
+#[test]
+fn nft_gated_auction() {
+    // Define participants
+    let owner = Addr::unchecked("owner");
+    let holder1 = Addr::unchecked("holder1");
+    let holder2 = Addr::unchecked("holder2");
+    let outsider = Addr::unchecked("outsider");
+
+    let mut app = App::new(|router, _api, storage| {
+        for sender in [&holder1, &holder2, &outsider] {
+            router
+                .bank
+                .init_balance(storage, sender, coins(10_000_000, UATOM))
+                .unwrap();
+        }
+    });
+
+    let collection = instantiate_cw721(&mut app, &holder1, "sc-1");
+    app.execute_contract(
+        Addr::unchecked("minter"),
+        collection.clone(),
+        &Cw721BaseExecuteMsg::<Extension, Empty>::Mint(MintMsg {
+            token_id: "sc-2".to_string(),
+            owner: holder2.to_string(),
+            token_uri: None,
+            extension: None,
+        }),
+        &[],
+    )
+    .unwrap();
+
+    let code_id = BidwasmContract::store_code(&mut app);
+
+    let contract = BidwasmContract::instantiate_with_msg(
+        &mut app,
+        code_id,
+        &owner,
+        "Bidwasm contract",
+        &InstantiateMsg {
+            nft_gate: Some(collection.to_string()),
+            ..base_msg(&owner)
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        contract.query_gating(&app).unwrap(),
+        GatingResp {
+            merkle_root: None,
+            group: None,
+            nft_gate: Some(collection.clone()),
+        }
+    );
+
+    // Bidders without a token of the collection cannot bid
+    let err = contract
+        .bid(&mut app, &outsider, &coins(1_000_000, UATOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotNftHolder {
+            bidder: outsider.to_string(),
+            collection: collection.to_string()
+        }
+    );
+
+    // Holders are found by looking up their tokens
+    contract
+        .bid(&mut app, &holder1, &coins(1_000_000, UATOM))
+        .unwrap();
+
+    // Or by pointing to a token they own, any other token or a missing one is
+    // rejected the same way
+    for token_id in ["sc-1", "sc-3"] {
+        let err = contract
+            .bid_with_token(&mut app, &outsider, &coins(2_000_000, UATOM), token_id)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotNftHolder {
+                bidder: outsider.to_string(),
+                collection: collection.to_string()
+            }
+        );
+    }
+    contract
+        .bid_with_token(&mut app, &holder2, &coins(2_000_000, UATOM), "sc-2")
+        .unwrap();
+
+    assert_eq!(
+        contract.query_highest_bid(&app).unwrap(),
+        BidResp {
+            address: holder2,
+            amount: Uint128::new(2_000_000)
+        }
+    );
+}
+// END --> NFT Gate Tests
 
 // START --> Complete Test
 #[test]
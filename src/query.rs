@@ -1,36 +1,226 @@
-use cosmwasm_std::{Deps, StdError, StdResult, Uint128};
+use cosmwasm_std::{Deps, Env, Order, StdError, StdResult, Uint128};
+use cw_storage_plus::Bound;
 
 use crate::{
-    msg::BidResp,
-    state::{Status, BIDS, STATE},
+    msg::{
+        AccessListResp, AccruedFeesResp, AllocationsResp, AuctionResp, BidResp, GatingResp,
+        ListAuctionsResp, LotResp, OperatorsResp, OwnershipResp, ScheduleResp, WinnerResp,
+    },
+    state::{
+        allocate, Commitment, Status, ACCESS_LIST, ACCRUED_FEES, BIDS, COMMITMENTS, CONFIGS,
+        OPERATORS, PENDING_OWNERS, STATES,
+    },
 };
 
-pub fn total_bid(deps: Deps, address: String) -> StdResult<Uint128> {
+// Pagination limits for the auction listing
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub fn total_bid(deps: Deps, auction_id: u64, address: String) -> StdResult<Uint128> {
     let address = deps.api.addr_validate(&address)?;
 
-    BIDS.load(deps.storage, &address)
+    BIDS.load(deps.storage, (auction_id, &address))
 }
 
-pub fn highest_bid(deps: Deps) -> StdResult<BidResp> {
-    match STATE.load(deps.storage)?.highest_bid {
+pub fn highest_bid(deps: Deps, auction_id: u64) -> StdResult<BidResp> {
+    match STATES.load(deps.storage, auction_id)?.highest_bid {
         Some((address, amount)) => Ok(BidResp { address, amount }),
         None => Err(StdError::not_found("The auction has not any bid")),
     }
 }
 
-pub fn is_closed(deps: Deps) -> StdResult<bool> {
-    match STATE.load(deps.storage)?.current_status {
-        Status::Closed => Ok(true),
-        _ => Ok(false),
-    }
+pub fn is_closed(deps: Deps, env: Env, auction_id: u64) -> StdResult<bool> {
+    Ok(STATES.load(deps.storage, auction_id)?.is_closed(&env.block))
 }
 
-pub fn winner(deps: Deps) -> StdResult<BidResp> {
-    if STATE.load(deps.storage)?.current_status == Status::Open {
+pub fn winner(deps: Deps, env: Env, auction_id: u64) -> StdResult<WinnerResp> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let state = STATES.load(deps.storage, auction_id)?;
+
+    if !state.is_closed(&env.block) {
         return Err(StdError::generic_err("The auction is yet open"));
     }
-    match STATE.load(deps.storage)?.highest_bid {
-        Some((address, amount)) => Ok(BidResp { address, amount }),
-        None => Err(StdError::not_found("The auction has not any bid")),
+    if state.current_status == Status::Cancelled {
+        return Err(StdError::generic_err("The auction has been cancelled"));
+    }
+    if config.quantity.is_some() {
+        return Err(StdError::generic_err(
+            "A multi-unit auction has allocations rather than a winner",
+        ));
+    }
+    match (
+        state.winner(&config),
+        &state.highest_bid,
+        config.reserve_price,
+    ) {
+        (Some((address, amount)), _, _) => Ok(WinnerResp::Won {
+            address: address.clone(),
+            amount: *amount,
+            clearing_price: state.clearing_price(&config).unwrap_or(*amount),
+            bought_now: state.bought_now,
+        }),
+        (None, Some((address, amount)), Some(reserve_price)) => Ok(WinnerResp::ReserveNotMet {
+            address: address.clone(),
+            amount: *amount,
+            reserve_price,
+        }),
+        _ => Err(StdError::not_found("The auction has not any bid")),
     }
 }
+
+pub fn schedule(deps: Deps, auction_id: u64) -> StdResult<ScheduleResp> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let state = STATES.load(deps.storage, auction_id)?;
+
+    Ok(ScheduleResp {
+        start: config.start,
+        end: state.end,
+        extension: state.extension,
+    })
+}
+
+pub fn min_next_bid(deps: Deps, auction_id: u64) -> StdResult<Uint128> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+
+    Ok(STATES.load(deps.storage, auction_id)?.min_next_bid(&config))
+}
+
+pub fn accrued_fees(deps: Deps, auction_id: u64) -> StdResult<AccruedFeesResp> {
+    let fees = ACCRUED_FEES
+        .prefix(auction_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+
+    Ok(AccruedFeesResp { fees })
+}
+
+pub fn lot(deps: Deps, auction_id: u64) -> StdResult<LotResp> {
+    let lot = CONFIGS.load(deps.storage, auction_id)?.lot;
+    let status = STATES.load(deps.storage, auction_id)?.current_status;
+
+    Ok(LotResp {
+        escrowed: lot.is_some() && status == Status::Open,
+        lot,
+    })
+}
+
+pub fn current_price(deps: Deps, env: Env, auction_id: u64) -> StdResult<Uint128> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+
+    match config.current_price(&env.block) {
+        Some(price) => Ok(price),
+        None => Err(StdError::generic_err(
+            "The auction has not a descending price",
+        )),
+    }
+}
+
+pub fn commitment(deps: Deps, auction_id: u64, address: String) -> StdResult<Commitment> {
+    let address = deps.api.addr_validate(&address)?;
+
+    COMMITMENTS.load(deps.storage, (auction_id, &address))
+}
+
+pub fn allocations(deps: Deps, auction_id: u64) -> StdResult<AllocationsResp> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    if config.quantity.is_none() {
+        return Err(StdError::generic_err("The auction is not a multi-unit one"));
+    }
+
+    let (clearing_price, allocations) = allocate(deps.storage, auction_id, &config)?;
+    Ok(AllocationsResp {
+        clearing_price,
+        allocations,
+    })
+}
+
+pub fn ownership(deps: Deps, auction_id: u64) -> StdResult<OwnershipResp> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+    let pending = PENDING_OWNERS.may_load(deps.storage, auction_id)?;
+
+    Ok(OwnershipResp {
+        owner: Some(config.owner).filter(|_| !config.renounced),
+        pending_expiry: pending.as_ref().map(|pending| pending.expiry),
+        pending_owner: pending.map(|pending| pending.owner),
+    })
+}
+
+pub fn gating(deps: Deps, auction_id: u64) -> StdResult<GatingResp> {
+    let config = CONFIGS.load(deps.storage, auction_id)?;
+
+    Ok(GatingResp {
+        merkle_root: config.merkle_root,
+        group: config.group,
+        nft_gate: config.nft_gate,
+    })
+}
+
+pub fn operators(deps: Deps, auction_id: u64) -> StdResult<OperatorsResp> {
+    let operators = OPERATORS
+        .prefix(auction_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+
+    Ok(OperatorsResp { operators })
+}
+
+pub fn access_list(
+    deps: Deps,
+    auction_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AccessListResp> {
+    let access = CONFIGS.load(deps.storage, auction_id)?.access;
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let members = ACCESS_LIST
+        .prefix(auction_id)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(AccessListResp { access, members })
+}
+
+pub fn list_auctions(
+    deps: Deps,
+    env: Env,
+    status: Option<Status>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListAuctionsResp> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    // Auctions are filtered by their status before being paginated, so a page
+    // is only shorter than the limit once the listing is over
+    let auctions = STATES
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| {
+            let (id, state) = item?;
+            Ok((id, state.status(&env.block)))
+        })
+        .filter(|item| match (item, &status) {
+            (Ok((_, auction_status)), Some(status)) => auction_status == status,
+            _ => true,
+        })
+        .take(limit)
+        .map(|item: StdResult<_>| {
+            let (id, status) = item?;
+            let config = CONFIGS.load(deps.storage, id)?;
+            Ok(AuctionResp {
+                id,
+                owner: config.owner,
+                description: config.description,
+                status,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListAuctionsResp { auctions })
+}
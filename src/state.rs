@@ -1,25 +1,449 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{
+    Addr, Binary, BlockInfo, Coin, Decimal, Order, StdResult, Storage, Timestamp, Uint128,
+};
+use cw20::Denom;
 use cw_storage_plus::{Item, Map};
+use cw_utils::{Expiration, Scheduled};
+use sha2::{Digest, Sha256};
 
 // Auction possible status
 #[cw_serde]
 pub enum Status {
+    // waiting for the lot to be deposited
+    Pending,
     Open,
     Closed,
+    // cancelled by the owner, every bid is refundable
+    Cancelled,
 }
 
 // Auction Configuration information
 #[cw_serde]
 pub struct Config {
-    /// denom of the token to bid
-    pub denom: String,
+    /// denom of the token to bid, either native or CW20
+    pub denom: Denom,
     // owner of the auction
     pub owner: Addr,
     // auction description
     pub description: String,
     // commission on each valid bid
-    pub commission: u128,
+    pub commission: CommissionPolicy,
+    // block height or time from which bids are accepted
+    pub start: Option<Scheduled>,
+    // block height or time after which the auction counts as closed
+    pub end: Option<Scheduled>,
+    // commission set aside to reward whoever settles the auction
+    pub keeper_reward: Uint128,
+    // deadline extension on late bids (anti-sniping)
+    pub soft_close: Option<SoftClose>,
+    // minimum highest bid for the auction to have a winner
+    pub reserve_price: Option<Uint128>,
+    // minimum amount for the first bid
+    pub starting_price: Option<Uint128>,
+    // minimum raise over the current highest bid
+    pub min_increment: Option<Increment>,
+    // commission recipients with their share, the owner takes it all if empty
+    pub commission_recipients: Vec<(Addr, Decimal)>,
+    // recipient of the winning bid, the owner if missing
+    pub proceeds_recipient: Option<Addr>,
+    // whether commissions are sent on each bid or accrued to be claimed
+    pub commission_mode: CommissionMode,
+    // on-chain asset escrowed and delivered to the winner
+    pub lot: Option<Lot>,
+    // ascending (English), descending price (Dutch) or sealed bid auction
+    pub kind: AuctionKind,
+    // price paid by the winner
+    pub pricing: Pricing,
+    // number of identical units on sale, bid for at a per-unit price
+    pub quantity: Option<u64>,
+    // total bid taking the lot at once, closing the auction
+    pub buy_now_price: Option<Uint128>,
+    // whether cancelling the auction refunds the commissions to the bidders
+    pub refund_commissions: bool,
+    // whether the owner gave up the management of the auction, it still
+    // receives the proceeds and commissions meant for the owner
+    pub renounced: bool,
+    // who is allowed to bid, according to the access list
+    pub access: AccessControl,
+    // root of the Merkle tree of the addresses eligible to bid, see
+    // `verify_merkle_proof`
+    pub merkle_root: Option<Binary>,
+    // cw4 group whose members are the only ones allowed to bid
+    pub group: Option<GroupGate>,
+    // CW721 collection the bidders must hold a token of
+    pub nft_gate: Option<Addr>,
+    // block time of the auction creation
+    pub created_at: Timestamp,
+}
+
+// Commission charged on the funds sent with each bid. Rates are expressed in
+// basis points (1/10_000) of the funds.
+#[cw_serde]
+pub enum CommissionPolicy {
+    // fixed amount of tokens
+    Flat(Uint128),
+    // share of the funds
+    Percentage {
+        bps: u64,
+    },
+    // share of the funds, bounded by a minimum and a maximum fee
+    ClampedPercentage {
+        bps: u64,
+        min: Uint128,
+        max: Uint128,
+    },
+    // share of the funds depending on the bracket they fall into, brackets are
+    // sorted by ascending lower bound
+    Tiered(Vec<CommissionTier>),
+}
+
+// Commission bracket applied to funds from `from` up to the next bracket
+#[cw_serde]
+pub struct CommissionTier {
+    pub from: Uint128,
+    pub bps: u64,
+}
+
+pub const MAX_BPS: u64 = 10_000;
+
+impl Default for CommissionPolicy {
+    fn default() -> Self {
+        CommissionPolicy::Flat(Uint128::zero())
+    }
+}
+
+impl CommissionPolicy {
+    // Check that rates do not exceed 100%, bounds are consistent and brackets
+    // are sorted
+    pub fn is_valid(&self) -> bool {
+        match self {
+            CommissionPolicy::Flat(_) => true,
+            CommissionPolicy::Percentage { bps } => *bps <= MAX_BPS,
+            CommissionPolicy::ClampedPercentage { bps, min, max } => *bps <= MAX_BPS && min <= max,
+            CommissionPolicy::Tiered(tiers) => {
+                !tiers.is_empty()
+                    && tiers.iter().all(|tier| tier.bps <= MAX_BPS)
+                    && tiers.windows(2).all(|pair| pair[0].from < pair[1].from)
+            }
+        }
+    }
+
+    // Commission due on the given funds
+    pub fn fee(&self, funds: Uint128) -> Uint128 {
+        let share = |bps: u64| funds.multiply_ratio(bps, MAX_BPS);
+
+        match self {
+            CommissionPolicy::Flat(fee) => *fee,
+            CommissionPolicy::Percentage { bps } => share(*bps),
+            CommissionPolicy::ClampedPercentage { bps, min, max } => share(*bps).clamp(*min, *max),
+            CommissionPolicy::Tiered(tiers) => tiers
+                .iter()
+                .rev()
+                .find(|tier| tier.from <= funds)
+                .map_or(Uint128::zero(), |tier| share(tier.bps)),
+        }
+    }
+}
+
+// On-chain asset sold by the auction
+#[cw_serde]
+pub enum Lot {
+    // native coins, sent with the instantiation
+    Native(Vec<Coin>),
+    // CW20 tokens, deposited by the owner with a CW20 send
+    Cw20 { token: Addr, amount: Uint128 },
+    // CW721 token, deposited by the owner with a CW721 send
+    Nft { collection: Addr, token_id: String },
+}
+
+// Type of auction, deciding how bids are processed
+#[cw_serde]
+#[derive(Default)]
+pub enum AuctionKind {
+    // bids raise the price until the auction is closed
+    #[default]
+    English,
+    // the price falls over time until the first bid takes the lot
+    Dutch(DutchAuction),
+    // bids are committed as hashes, then revealed before the auction end
+    Sealed(SealedAuction),
+}
+
+// Price paid by the winner of the auction
+#[cw_serde]
+#[derive(Default)]
+pub enum Pricing {
+    // the winning bid
+    #[default]
+    FirstPrice,
+    // the runner-up bid (Vickrey), or the reserve or starting price if higher
+    SecondPrice,
+}
+
+// Descending price, starting at its start (or creation) time
+#[cw_serde]
+pub struct DutchAuction {
+    pub start_price: Uint128,
+    pub floor_price: Uint128,
+    pub decline: Decline,
+}
+
+// How the price of a Dutch auction falls, every duration is expressed in
+// seconds
+#[cw_serde]
+pub enum Decline {
+    // continuously, reaching the floor price after `duration`
+    Linear { duration: u64 },
+    // by `amount` every `interval`
+    Stepwise { interval: u64, amount: Uint128 },
+}
+
+impl DutchAuction {
+    pub fn is_valid(&self) -> bool {
+        let is_valid_decline = match self.decline {
+            Decline::Linear { duration } => duration > 0,
+            Decline::Stepwise { interval, amount } => interval > 0 && !amount.is_zero(),
+        };
+        self.start_price >= self.floor_price && is_valid_decline
+    }
+
+    // Price once `elapsed` seconds have passed since the price started falling
+    pub fn price(&self, elapsed: u64) -> Uint128 {
+        let range = self.start_price - self.floor_price;
+        let drop = match self.decline {
+            Decline::Linear { duration } => range.multiply_ratio(elapsed.min(duration), duration),
+            Decline::Stepwise { interval, amount } => {
+                amount.saturating_mul(Uint128::from(elapsed / interval))
+            }
+        };
+        self.start_price - drop.min(range)
+    }
+}
+
+// Sealed bids are committed until `commit_end` and revealed until the end of
+// the auction
+#[cw_serde]
+pub struct SealedAuction {
+    pub commit_end: Scheduled,
+    pub unrevealed: UnrevealedDeposits,
+}
+
+// What happens to the deposits of bids never revealed
+#[cw_serde]
+pub enum UnrevealedDeposits {
+    // retracted by their bidders, like losing bids
+    Refund,
+    // sent to the proceeds recipient when the auction is closed
+    Forfeit,
+}
+
+// Sealed bid waiting to be revealed
+#[cw_serde]
+pub struct Commitment {
+    // hash of the bid, see `commitment_hash`
+    pub hash: Binary,
+    // funds sent along with the commitment, covering the bid and commission
+    pub deposit: Uint128,
+}
+
+// Hash a sealed bid commits to: the sha256 of the bidder address, the amount
+// as 16 big-endian bytes and the salt
+pub fn commitment_hash(bidder: &Addr, amount: Uint128, salt: &[u8]) -> Binary {
+    let hash = Sha256::new()
+        .chain_update(bidder.as_bytes())
+        .chain_update(amount.u128().to_be_bytes())
+        .chain_update(salt)
+        .finalize();
+    Binary::from(hash.as_slice())
+}
+
+// Check that `bidder` is a leaf of the Merkle tree with the given root. Leaves
+// are the sha256 of the addresses, and each node is the sha256 of its two
+// children sorted in ascending order, so the proof only lists the siblings.
+pub fn verify_merkle_proof(root: &Binary, bidder: &Addr, proof: &[Binary]) -> bool {
+    let leaf = Sha256::digest(bidder.as_bytes()).to_vec();
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node.as_slice() <= sibling.as_slice() {
+            (node.as_slice(), sibling.as_slice())
+        } else {
+            (sibling.as_slice(), node.as_slice())
+        };
+        Sha256::new()
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+            .to_vec()
+    });
+    computed == root.as_slice()
+}
+
+// Bid for some units of a multi-unit auction, its deposit is kept in `BIDS`
+#[cw_serde]
+pub struct UnitBid {
+    pub price: Uint128,
+    pub quantity: u64,
+    // position of the bid, earlier bids win ties on price
+    pub seq: u64,
+}
+
+// Units of a multi-unit auction allocated to each winning bidder
+pub type Allocations = Vec<(Addr, u64)>;
+
+// Allocate the supply of a multi-unit auction to the highest bids meeting the
+// reserve price, the last winning bid may be partially filled. Returns the
+// uniform clearing price, which is the lowest winning price, along with the
+// units allocated to each winning bidder.
+pub fn allocate(
+    storage: &dyn Storage,
+    auction_id: u64,
+    config: &Config,
+) -> StdResult<(Option<Uint128>, Allocations)> {
+    let mut bids = UNIT_BIDS
+        .prefix(auction_id)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    bids.retain(|(_, bid)| {
+        config
+            .reserve_price
+            .is_none_or(|reserve_price| bid.price >= reserve_price)
+    });
+    bids.sort_by(|(_, a), (_, b)| b.price.cmp(&a.price).then(a.seq.cmp(&b.seq)));
+
+    let mut supply = config.quantity.unwrap_or_default();
+    let mut clearing_price = None;
+    let mut allocations = vec![];
+    for (bidder, bid) in bids {
+        if supply == 0 {
+            break;
+        }
+        let units = bid.quantity.min(supply);
+        supply -= units;
+        clearing_price = Some(bid.price);
+        allocations.push((bidder, units));
+    }
+
+    Ok((clearing_price, allocations))
+}
+
+// How commissions reach their recipients
+#[cw_serde]
+#[derive(Default)]
+pub enum CommissionMode {
+    // sent to the recipients on each bid
+    #[default]
+    Push,
+    // accrued in the contract and claimed by the recipients
+    Accrue,
+}
+
+// Minimum raise over the current highest bid
+#[cw_serde]
+pub enum Increment {
+    // fixed amount of tokens
+    Absolute(Uint128),
+    // share of the current highest bid
+    Percentage(Decimal),
+}
+
+// Soft close configuration, every duration is expressed in seconds
+#[cw_serde]
+pub struct SoftClose {
+    // bids placed this close to the end extend it
+    pub window: u64,
+    // how much the end moves forward on each late bid
+    pub extension: u64,
+    // maximum total extension of the end
+    pub max_extension: u64,
+}
+
+// Who can bid on the auction
+#[cw_serde]
+#[derive(Default)]
+pub enum AccessControl {
+    // anyone but the owner and the operators
+    #[default]
+    Open,
+    // only the members of the access list
+    Allowlist,
+    // anyone but the members of the access list
+    Denylist,
+}
+
+// External cw4 group gating the bids
+#[cw_serde]
+pub struct GroupGate {
+    pub group: Addr,
+    // members with a lower weight cannot bid
+    pub min_weight: u64,
+}
+
+// Action an operator can perform on behalf of the owner
+#[cw_serde]
+pub enum Permission {
+    Close,
+    Cancel,
+    // update the description of the auction
+    UpdateMetadata,
+    // manage who is allowed to bid
+    ManageAccess,
+}
+
+// Ownership transfer waiting to be accepted by the new owner
+#[cw_serde]
+pub struct PendingOwner {
+    pub owner: Addr,
+    pub expiry: Expiration,
+}
+
+impl Config {
+    // Whether `sender` can manage the auction
+    pub fn is_owner(&self, sender: &str) -> bool {
+        !self.renounced && self.owner == sender
+    }
+
+    // Who receives the winning bid
+    pub fn proceeds_recipient(&self) -> &Addr {
+        self.proceeds_recipient.as_ref().unwrap_or(&self.owner)
+    }
+
+    // Split a commission among its recipients, the rounding dust goes to the
+    // first recipient
+    pub fn commission_split(&self, amount: Uint128) -> Vec<(Addr, Uint128)> {
+        if self.commission_recipients.is_empty() {
+            return vec![(self.owner.clone(), amount)];
+        }
+
+        let mut split: Vec<_> = self
+            .commission_recipients
+            .iter()
+            .map(|(recipient, share)| (recipient.clone(), amount * *share))
+            .collect();
+        let distributed: Uint128 = split.iter().map(|(_, amount)| *amount).sum();
+        split[0].1 += amount - distributed;
+
+        split
+    }
+
+    // Current price of a Dutch auction, which starts falling at the start time
+    // if given, or at the creation otherwise
+    pub fn current_price(&self, block: &BlockInfo) -> Option<Uint128> {
+        let AuctionKind::Dutch(dutch) = &self.kind else {
+            return None;
+        };
+        let from = match self.start {
+            Some(Scheduled::AtTime(start)) => start.max(self.created_at),
+            _ => self.created_at,
+        };
+        Some(dutch.price(block.time.seconds().saturating_sub(from.seconds())))
+    }
+
+    // Whether the auction has reached its start (always true without start)
+    pub fn has_started(&self, block: &BlockInfo) -> bool {
+        self.start
+            .as_ref()
+            .is_none_or(|start| start.is_triggered(block))
+    }
 }
 
 // Auction Current status
@@ -29,8 +453,137 @@ pub struct State {
     pub current_status: Status,
     // current highest bid to the auction
     pub highest_bid: Option<(Addr, Uint128)>,
+    // highest bid from a bidder other than the leading one
+    pub runner_up: Option<(Addr, Uint128)>,
+    // commissions kept by the contract to pay the keeper reward
+    pub commission_pool: Uint128,
+    // effective end of the auction, including soft close extensions
+    pub end: Option<Scheduled>,
+    // total seconds the end has been extended by late bids
+    pub extension: u64,
+    // deposits of sealed bids not revealed yet
+    pub unrevealed_deposits: Uint128,
+    // bids placed on a multi-unit auction, ordering the ones with equal price
+    pub unit_bids: u64,
+    // whether the auction was closed by a bid reaching the buy-it-now price
+    pub bought_now: bool,
+}
+
+impl State {
+    // Whether the auction has reached its end (always false without end)
+    pub fn has_ended(&self, block: &BlockInfo) -> bool {
+        self.end.as_ref().is_some_and(|end| end.is_triggered(block))
+    }
+
+    // Whether the auction has been closed or cancelled by a transaction
+    pub fn is_final(&self) -> bool {
+        matches!(self.current_status, Status::Closed | Status::Cancelled)
+    }
+
+    // The auction is closed either explicitly (or cancelled) or because its
+    // end has passed
+    pub fn is_closed(&self, block: &BlockInfo) -> bool {
+        self.is_final() || self.has_ended(block)
+    }
+
+    // Status of the auction, counting it as closed once its end has passed
+    pub fn status(&self, block: &BlockInfo) -> Status {
+        match &self.current_status {
+            Status::Cancelled => Status::Cancelled,
+            _ if self.is_closed(block) => Status::Closed,
+            status => status.clone(),
+        }
+    }
+
+    // The lowest total bid that would take the lead of the auction
+    pub fn min_next_bid(&self, config: &Config) -> Uint128 {
+        match &self.highest_bid {
+            Some((_, amount)) => {
+                let increment = match config.min_increment {
+                    Some(Increment::Absolute(increment)) => increment,
+                    Some(Increment::Percentage(share)) => *amount * share,
+                    None => Uint128::zero(),
+                };
+                // The new bid must always be strictly greater than the highest
+                *amount + increment.max(Uint128::one())
+            }
+            None => config
+                .starting_price
+                .unwrap_or_default()
+                .max(Uint128::one()),
+        }
+    }
+
+    // Record a bid of `amount` by `bidder`, keeping track of the runner-up
+    pub fn record_bid(&mut self, bidder: &Addr, amount: Uint128) {
+        match &self.highest_bid {
+            // the leader raising its own bid does not change the runner-up
+            Some((leader, _)) if leader == bidder => {}
+            Some((_, highest)) if amount > *highest => {
+                self.runner_up = self.highest_bid.take();
+            }
+            Some(_) => {
+                if self
+                    .runner_up
+                    .as_ref()
+                    .is_none_or(|(_, runner_up)| amount > *runner_up)
+                {
+                    self.runner_up = Some((bidder.clone(), amount));
+                }
+                return;
+            }
+            None => {}
+        }
+        self.highest_bid = Some((bidder.clone(), amount));
+    }
+
+    // Price paid by the winner, if any
+    pub fn clearing_price(&self, config: &Config) -> Option<Uint128> {
+        let (_, amount) = self.winner(config)?;
+        let price = match config.pricing {
+            // the buy-it-now price is paid as it is
+            _ if self.bought_now => *amount,
+            Pricing::FirstPrice => *amount,
+            Pricing::SecondPrice => self
+                .runner_up
+                .as_ref()
+                .map(|(_, runner_up)| *runner_up)
+                .unwrap_or_default()
+                .max(config.reserve_price.unwrap_or_default())
+                .max(config.starting_price.unwrap_or_default())
+                .min(*amount),
+        };
+        Some(price)
+    }
+
+    // The highest bid, if it meets the reserve price
+    pub fn winner(&self, config: &Config) -> Option<&(Addr, Uint128)> {
+        self.highest_bid.as_ref().filter(|(_, amount)| {
+            config
+                .reserve_price
+                .is_none_or(|reserve_price| *amount >= reserve_price)
+        })
+    }
 }
 
-pub const CONFIG: Item<Config> = Item::new("config");
-pub const STATE: Item<State> = Item::new("state");
-pub const BIDS: Map<&Addr, Uint128> = Map::new("bids");
+// number of auctions created, which is also the id of the next one
+pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
+pub const CONFIGS: Map<u64, Config> = Map::new("configs");
+pub const STATES: Map<u64, State> = Map::new("states");
+pub const BIDS: Map<(u64, &Addr), Uint128> = Map::new("bids");
+// sealed bids of each bidder waiting to be revealed
+pub const COMMITMENTS: Map<(u64, &Addr), Commitment> = Map::new("commitments");
+// bids of each bidder on a multi-unit auction
+pub const UNIT_BIDS: Map<(u64, &Addr), UnitBid> = Map::new("unit_bids");
+// members of the access list of each auction
+pub const ACCESS_LIST: Map<(u64, &Addr), ()> = Map::new("access_list");
+// Merkle root each bidder proved its eligibility against
+pub const VERIFIED: Map<(u64, &Addr), Binary> = Map::new("verified");
+// operators of each auction, with their permissions
+pub const OPERATORS: Map<(u64, &Addr), Vec<Permission>> = Map::new("operators");
+// ownership transfers waiting to be accepted
+pub const PENDING_OWNERS: Map<u64, PendingOwner> = Map::new("pending_owners");
+// commissions paid by each bidder, refunded if the auction is cancelled
+pub const COMMISSIONS: Map<(u64, &Addr), Uint128> = Map::new("commissions");
+// commissions accrued by each recipient and not claimed yet
+pub const ACCRUED_FEES: Map<(u64, &Addr), Uint128> = Map::new("accrued_fees");